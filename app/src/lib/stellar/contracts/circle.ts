@@ -32,6 +32,55 @@ export interface CircleConfig {
   periodLength: bigint; // Seconds (30 days = 2592000)
   gracePeriod: bigint; // Seconds (7 days = 604800)
   lateFeePercent: number; // 0-50
+  autoPayout?: boolean; // Pay out with the final contribution (default true)
+  defaultPolicy?: DefaultPolicy; // How settled rounds handle missed payments
+  deposit?: DepositRequirement; // Security deposit to join (default none)
+  payoutMode?: PayoutMode; // How each round's recipient is chosen
+  payoutOrder?: PayoutOrder; // How payout positions are assigned at the start
+  bidWindow?: bigint; // Seconds of bidding or need voting at the start of each round
+  formingDeadline?: bigint; // Unix time after which a forming circle can expire (0 for none)
+  minMembers?: number; // Members needed to start short at the deadline (3 or more)
+}
+
+/**
+ * How a round is settled when members miss their contribution
+ */
+export type DefaultPolicy = "PayoutCollected" | "CoverShortfall" | "Pause";
+
+/**
+ * Security deposit required to join: a fixed amount (USDC stroops) or a
+ * multiple of the contribution amount
+ */
+export type DepositRequirement = { fixed: bigint } | { multiple: number };
+
+/**
+ * How each round's recipient is chosen
+ */
+export type PayoutMode = "Rotation" | "OpenAuction" | "SealedAuction";
+
+/**
+ * How payout positions are assigned when the circle starts
+ */
+export type PayoutOrder = "JoinOrder" | "Random" | "CreditScore" | "NeedVote";
+
+/**
+ * Encode a unit variant of a contract enum (e.g. `PayoutMode::Rotation`)
+ */
+function enumVariantToScVal(variant: string): xdr.ScVal {
+  return xdr.ScVal.scvVec([symbolToScVal(variant)]);
+}
+
+/**
+ * Encode a `DepositRequirement`, `None` when no deposit is required
+ */
+function depositToScVal(deposit?: DepositRequirement): xdr.ScVal {
+  if (!deposit) {
+    return enumVariantToScVal("None");
+  }
+  if ("fixed" in deposit) {
+    return xdr.ScVal.scvVec([symbolToScVal("Fixed"), i128ToScVal(deposit.fixed)]);
+  }
+  return xdr.ScVal.scvVec([symbolToScVal("Multiple"), u32ToScVal(deposit.multiple)]);
 }
 
 /**
@@ -81,7 +130,17 @@ export class CircleContract {
 
     // Build the CircleConfig struct as ScVal
     // IMPORTANT: Soroban requires ScMap keys to be sorted alphabetically
+    // Options left out of the config are sent with the contract defaults:
+    // rotation payouts in join order, no deposit, no forming deadline
     const configScVal = xdr.ScVal.scvMap([
+      new xdr.ScMapEntry({
+        key: symbolToScVal("auto_payout"),
+        val: xdr.ScVal.scvBool(config.autoPayout ?? true),
+      }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("bid_window"),
+        val: u64ToScVal(config.bidWindow ?? BigInt(0)),
+      }),
       new xdr.ScMapEntry({
         key: symbolToScVal("contribution_amount"),
         val: i128ToScVal(config.contributionAmount),
@@ -90,6 +149,18 @@ export class CircleContract {
         key: symbolToScVal("contribution_token"),
         val: addressToScVal(USDC_CONTRACT_ADDRESS),
       }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("default_policy"),
+        val: enumVariantToScVal(config.defaultPolicy ?? "PayoutCollected"),
+      }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("deposit"),
+        val: depositToScVal(config.deposit),
+      }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("forming_deadline"),
+        val: u64ToScVal(config.formingDeadline ?? BigInt(0)),
+      }),
       new xdr.ScMapEntry({
         key: symbolToScVal("grace_period"),
         val: u64ToScVal(config.gracePeriod),
@@ -98,10 +169,22 @@ export class CircleContract {
         key: symbolToScVal("late_fee_percent"),
         val: u32ToScVal(config.lateFeePercent),
       }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("min_members"),
+        val: u32ToScVal(config.minMembers ?? config.totalMembers),
+      }),
       new xdr.ScMapEntry({
         key: symbolToScVal("name"),
         val: symbolToScVal(config.name),
       }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("payout_mode"),
+        val: enumVariantToScVal(config.payoutMode ?? "Rotation"),
+      }),
+      new xdr.ScMapEntry({
+        key: symbolToScVal("payout_order"),
+        val: enumVariantToScVal(config.payoutOrder ?? "JoinOrder"),
+      }),
       new xdr.ScMapEntry({
         key: symbolToScVal("period_length"),
         val: u64ToScVal(config.periodLength),
@@ -0,0 +1,487 @@
+//! Halo Protocol Badge Contract
+//!
+//! This contract issues a soulbound (non-transferable) badge per wallet that reflects
+//! the wallet's current score tier in the Credit contract. Partners can check a badge
+//! through the familiar token read interface (`balance`, `name`, `symbol`, `decimals`)
+//! or read its metadata with `get_badge`.
+//!
+//! Key Features:
+//! - One badge per wallet, minted once the wallet reaches the minimum tier
+//! - Anyone can refresh a wallet's badge to match its current tier
+//! - Badges are upgraded, downgraded or burned as the tier changes
+//! - Transfers and approvals always fail
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, Address, Env, String,
+    Symbol,
+};
+
+/// Subset of the Credit contract interface used to look up a wallet's tier
+#[allow(dead_code)]
+#[contractclient(name = "CreditClient")]
+trait CreditInterface {
+    fn get_tier_by_wallet(
+        env: Env,
+        wallet: Address,
+    ) -> Result<Option<ScoreTier>, soroban_sdk::Error>;
+    fn get_score_version(env: Env) -> u32;
+}
+
+/// Storage keys for the contract
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// Admin address with privileged access
+    Admin,
+    /// Credit contract the badges reflect
+    CreditContract,
+    /// Minimum tier required to hold a badge
+    MinTier,
+    /// Badge held by a wallet
+    Badge(Address),
+    /// Number of badges currently held
+    BadgeCount,
+}
+
+/// Contract errors
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BadgeError {
+    /// Contract has already been initialized
+    AlreadyInitialized = 1,
+    /// Contract has not been initialized
+    NotInitialized = 2,
+    /// Caller is not authorized
+    Unauthorized = 3,
+    /// Badges cannot be transferred or approved
+    NonTransferable = 4,
+    /// The Credit contract could not report the wallet's tier (unbound or frozen)
+    TierUnavailable = 5,
+}
+
+/// Score tier, mirroring the Credit contract's `ScoreTier`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[contracttype]
+pub enum ScoreTier {
+    /// 300-449: Building credit
+    Building,
+    /// 450-599: Fair
+    Fair,
+    /// 600-749: Good
+    Good,
+    /// 750-850: Excellent
+    Excellent,
+}
+
+/// Badge metadata
+#[derive(Clone)]
+#[contracttype]
+pub struct Badge {
+    /// Tier the badge currently represents
+    pub tier: ScoreTier,
+    /// Timestamp the badge was minted
+    pub issued_at: u64,
+    /// Timestamp the badge was last changed
+    pub updated_at: u64,
+    /// Score algorithm version the tier was computed with
+    pub score_version: u32,
+}
+
+#[contract]
+pub struct HaloBadge;
+
+#[contractimpl]
+impl HaloBadge {
+    /// Initialize the contract.
+    ///
+    /// # Arguments
+    /// * `admin` - The address that will have admin privileges
+    /// * `credit_contract` - The Credit contract badges reflect
+    /// * `min_tier` - Minimum tier required to hold a badge
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        credit_contract: Address,
+        min_tier: ScoreTier,
+    ) -> Result<(), BadgeError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(BadgeError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::CreditContract, &credit_contract);
+        env.storage().instance().set(&DataKey::MinTier, &min_tier);
+        env.storage().instance().set(&DataKey::BadgeCount, &0u64);
+
+        env.storage()
+            .instance()
+            .extend_ttl(100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events().publish(
+            (Symbol::new(&env, "initialized"),),
+            (admin, credit_contract, min_tier),
+        );
+
+        Ok(())
+    }
+
+    /// Update the minimum tier. Only callable by admin.
+    /// Existing badges follow the new minimum on their next refresh.
+    pub fn set_min_tier(env: Env, min_tier: ScoreTier) -> Result<(), BadgeError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(BadgeError::NotInitialized)?;
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::MinTier, &min_tier);
+
+        env.events()
+            .publish((Symbol::new(&env, "min_tier_set"),), min_tier);
+
+        Ok(())
+    }
+
+    /// Sync a wallet's badge with its current tier in the Credit contract. Can be called by anyone.
+    ///
+    /// Mints a badge if the wallet has reached the minimum tier and holds none, updates it
+    /// if the tier changed, and burns it if the wallet no longer qualifies.
+    ///
+    /// # Returns
+    /// The wallet's badge after the refresh, if it holds one
+    pub fn refresh(env: Env, wallet: Address) -> Result<Option<Badge>, BadgeError> {
+        let credit_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CreditContract)
+            .ok_or(BadgeError::NotInitialized)?;
+        let min_tier: ScoreTier = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinTier)
+            .ok_or(BadgeError::NotInitialized)?;
+
+        // Wallets without credit data have no tier. Any other failure leaves the
+        // badge untouched rather than burning it.
+        let credit = CreditClient::new(&env, &credit_contract);
+        let tier = match credit.try_get_tier_by_wallet(&wallet) {
+            Ok(Ok(tier)) => tier.filter(|tier| *tier >= min_tier),
+            _ => return Err(BadgeError::TierUnavailable),
+        };
+
+        let key = DataKey::Badge(wallet.clone());
+        let existing: Option<Badge> = env.storage().persistent().get(&key);
+        let current_time = env.ledger().timestamp();
+
+        match (existing, tier) {
+            (None, Some(tier)) => {
+                let badge = Badge {
+                    tier,
+                    issued_at: current_time,
+                    updated_at: current_time,
+                    score_version: credit.get_score_version(),
+                };
+                Self::store_badge(&env, &key, &badge);
+                Self::adjust_badge_count(&env, 1);
+
+                env.events()
+                    .publish((Symbol::new(&env, "badge_minted"),), (wallet, tier));
+
+                Ok(Some(badge))
+            }
+            (Some(mut badge), Some(tier)) => {
+                let score_version = credit.get_score_version();
+                if badge.tier != tier || badge.score_version != score_version {
+                    let previous = badge.tier;
+                    badge.tier = tier;
+                    badge.score_version = score_version;
+                    badge.updated_at = current_time;
+
+                    env.events().publish(
+                        (Symbol::new(&env, "badge_updated"),),
+                        (wallet, previous, tier),
+                    );
+                }
+                Self::store_badge(&env, &key, &badge);
+
+                Ok(Some(badge))
+            }
+            (Some(badge), None) => {
+                env.storage().persistent().remove(&key);
+                Self::adjust_badge_count(&env, -1);
+
+                env.events()
+                    .publish((Symbol::new(&env, "badge_burned"),), (wallet, badge.tier));
+
+                Ok(None)
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    // ============ Query Functions ============
+
+    /// Get a wallet's badge metadata. PUBLIC - used by SDK.
+    pub fn get_badge(env: Env, wallet: Address) -> Option<Badge> {
+        env.storage().persistent().get(&DataKey::Badge(wallet))
+    }
+
+    /// Get the tier a wallet's badge represents. PUBLIC - used by SDK.
+    pub fn tier_of(env: Env, wallet: Address) -> Option<ScoreTier> {
+        Self::get_badge(env, wallet).map(|badge| badge.tier)
+    }
+
+    /// Get the number of badges currently held.
+    pub fn get_badge_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::BadgeCount)
+            .unwrap_or(0)
+    }
+
+    /// Get the minimum tier required to hold a badge.
+    pub fn get_min_tier(env: Env) -> Result<ScoreTier, BadgeError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinTier)
+            .ok_or(BadgeError::NotInitialized)
+    }
+
+    /// Get the Credit contract address.
+    pub fn get_credit_contract(env: Env) -> Result<Address, BadgeError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::CreditContract)
+            .ok_or(BadgeError::NotInitialized)
+    }
+
+    /// Get the admin address.
+    pub fn get_admin(env: Env) -> Result<Address, BadgeError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(BadgeError::NotInitialized)
+    }
+
+    // ============ Token Interface ============
+
+    /// Badge balance of a wallet (1 if it holds a badge, 0 otherwise).
+    pub fn balance(env: Env, id: Address) -> i128 {
+        if env.storage().persistent().has(&DataKey::Badge(id)) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Badges are indivisible.
+    pub fn decimals(_env: Env) -> u32 {
+        0
+    }
+
+    /// Token name.
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, "Halo Credit Badge")
+    }
+
+    /// Token symbol.
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "HALOBADGE")
+    }
+
+    /// Badges cannot be approved for spending, so allowances are always 0.
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        0
+    }
+
+    /// Always fails - badges are soulbound.
+    pub fn transfer(
+        _env: Env,
+        _from: Address,
+        _to: Address,
+        _amount: i128,
+    ) -> Result<(), BadgeError> {
+        Err(BadgeError::NonTransferable)
+    }
+
+    /// Always fails - badges are soulbound.
+    pub fn transfer_from(
+        _env: Env,
+        _spender: Address,
+        _from: Address,
+        _to: Address,
+        _amount: i128,
+    ) -> Result<(), BadgeError> {
+        Err(BadgeError::NonTransferable)
+    }
+
+    /// Always fails - badges are soulbound.
+    pub fn approve(
+        _env: Env,
+        _from: Address,
+        _spender: Address,
+        _amount: i128,
+        _expiration_ledger: u32,
+    ) -> Result<(), BadgeError> {
+        Err(BadgeError::NonTransferable)
+    }
+
+    // ============ Internal Functions ============
+
+    fn store_badge(env: &Env, key: &DataKey, badge: &Badge) {
+        env.storage().persistent().set(key, badge);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+
+    fn adjust_badge_count(env: &Env, delta: i64) {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::BadgeCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::BadgeCount, &count.saturating_add_signed(delta));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use halo_credit::{HaloCredit, HaloCreditClient};
+    use halo_identity::{HaloIdentity, HaloIdentityClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{BytesN, Env};
+
+    struct Setup<'a> {
+        env: Env,
+        credit: HaloCreditClient<'a>,
+        identity: HaloIdentityClient<'a>,
+        badge: HaloBadgeClient<'a>,
+        circle_contract: Address,
+    }
+
+    fn setup<'a>(min_tier: ScoreTier) -> Setup<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+
+        let identity_id = env.register_contract(None, HaloIdentity);
+        let identity = HaloIdentityClient::new(&env, &identity_id);
+        identity.initialize(&admin);
+
+        let credit_id = env.register_contract(None, HaloCredit);
+        let credit = HaloCreditClient::new(&env, &credit_id);
+        credit.initialize(&admin);
+        credit.authorize_contract(&circle_contract);
+        credit.set_identity_contract(&identity_id);
+
+        let badge_id = env.register_contract(None, HaloBadge);
+        let badge = HaloBadgeClient::new(&env, &badge_id);
+        badge.initialize(&admin, &credit_id, &min_tier);
+
+        Setup { env, credit, identity, badge, circle_contract }
+    }
+
+    fn create_id(env: &Env, seed: u8) -> BytesN<32> {
+        let mut bytes = [0u8; 32];
+        bytes[0] = seed;
+        BytesN::from_array(env, &bytes)
+    }
+
+    #[test]
+    fn test_mint_upgrade_and_burn() {
+        let s = setup(ScoreTier::Fair);
+        let wallet = Address::generate(&s.env);
+        let unique_id = create_id(&s.env, 1);
+        let circle_id = create_id(&s.env, 2);
+        let usdc = Address::generate(&s.env);
+
+        // No identity yet
+        assert_eq!(s.badge.try_refresh(&wallet).err(), Some(Ok(BadgeError::TierUnavailable)));
+        assert_eq!(s.badge.balance(&wallet), 0);
+
+        // Bound, but no credit data
+        s.identity.bind_wallet(&unique_id, &wallet);
+        assert!(s.badge.refresh(&wallet).is_none());
+
+        s.credit.record_payment(&s.circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        s.env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+        let badge = s.badge.refresh(&wallet).unwrap();
+        assert_eq!(badge.tier, ScoreTier::Good);
+        assert_eq!(badge.issued_at, 1_000);
+        assert_eq!(badge.score_version, s.credit.get_score_version());
+        assert_eq!(s.badge.balance(&wallet), 1);
+        assert_eq!(s.badge.get_badge_count(), 1);
+
+        // Missed payments drop the wallet below the minimum tier
+        for round in 2..=5 {
+            s.credit.record_missed_payment(&s.circle_contract, &unique_id, &circle_id, &round);
+        }
+        assert!(s.badge.refresh(&wallet).is_none());
+        assert_eq!(s.badge.balance(&wallet), 0);
+        assert_eq!(s.badge.get_badge_count(), 0);
+
+        // Lowering the minimum re-mints at the current tier
+        s.badge.set_min_tier(&ScoreTier::Building);
+        let badge = s.badge.refresh(&wallet).unwrap();
+        assert_eq!(Some(badge.tier), s.badge.tier_of(&wallet));
+        assert!(badge.tier < ScoreTier::Fair);
+    }
+
+    #[test]
+    fn test_badges_are_soulbound() {
+        let s = setup(ScoreTier::Building);
+        let wallet = Address::generate(&s.env);
+        let other = Address::generate(&s.env);
+        let unique_id = create_id(&s.env, 1);
+        let circle_id = create_id(&s.env, 2);
+        let usdc = Address::generate(&s.env);
+
+        s.identity.bind_wallet(&unique_id, &wallet);
+        s.credit.record_payment(&s.circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        s.badge.refresh(&wallet);
+
+        assert_eq!(s.badge.try_transfer(&wallet, &other, &1).err(), Some(Ok(BadgeError::NonTransferable)));
+        assert_eq!(s.badge.try_transfer_from(&other, &wallet, &other, &1).err(), Some(Ok(BadgeError::NonTransferable)));
+        assert_eq!(s.badge.try_approve(&wallet, &other, &1, &100).err(), Some(Ok(BadgeError::NonTransferable)));
+        assert_eq!(s.badge.allowance(&wallet, &other), 0);
+        assert_eq!(s.badge.balance(&wallet), 1);
+        assert_eq!(s.badge.balance(&other), 0);
+        assert_eq!(s.badge.decimals(), 0);
+    }
+
+    #[test]
+    fn test_frozen_profile_keeps_badge() {
+        let s = setup(ScoreTier::Building);
+        let wallet = Address::generate(&s.env);
+        let unique_id = create_id(&s.env, 1);
+        let circle_id = create_id(&s.env, 2);
+        let usdc = Address::generate(&s.env);
+
+        s.identity.bind_wallet(&unique_id, &wallet);
+        s.credit.record_payment(&s.circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        let badge = s.badge.refresh(&wallet).unwrap();
+
+        // A frozen profile cannot be read, so the badge is neither burned nor changed
+        s.credit.freeze_credit(&wallet, &unique_id);
+        assert_eq!(s.badge.try_refresh(&wallet).err(), Some(Ok(BadgeError::TierUnavailable)));
+        let held = s.badge.get_badge(&wallet).unwrap();
+        assert_eq!(held.tier, badge.tier);
+        assert_eq!(held.updated_at, badge.updated_at);
+        assert_eq!(s.badge.balance(&wallet), 1);
+        assert_eq!(s.badge.get_badge_count(), 1);
+    }
+}
@@ -0,0 +1,593 @@
+//! Halo Protocol Bureau Export
+//!
+//! Turns a JSON dump of Halo credit events into a Metro 2 style furnishing file.
+//! Each circle membership (unique ID + circle ID) is reported as one tradeline.
+//!
+//! File layout (fixed-width records, `RECORD_LENGTH` characters each):
+//! - Header record - furnisher details and activity date
+//! - Base segment - one per tradeline
+//! - Trailer record - record count and counts per account status
+//!
+//! Account status codes:
+//! - `11` Current - latest payment on time
+//! - `71` Late - latest payment late
+//! - `78` Seriously past due - latest payment missed
+//! - `97` Charged off - circle defaulted
+//! - `13` Paid/closed - circle completed
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Length of every record in the file
+pub const RECORD_LENGTH: usize = 250;
+/// Number of months covered by the payment history profile
+pub const HISTORY_LENGTH: usize = 24;
+/// Reference units per whole dollar (amounts use 6 decimals, like USDC)
+pub const UNITS_PER_DOLLAR: i128 = 1_000_000;
+/// Metro 2 portfolio type for open accounts
+const PORTFOLIO_TYPE: &str = "O";
+/// Metro 2 account type for unsecured accounts - circles carry no collateral
+const ACCOUNT_TYPE: &str = "01";
+
+/// Credit event kinds, named as in the app's `credit_events` table
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    /// Contribution paid on time
+    PaymentOntime,
+    /// Contribution paid late
+    PaymentLate,
+    /// Contribution missed
+    PaymentMissed,
+    /// Circle completed successfully
+    CircleCompleted,
+    /// Circle ended in default
+    CircleDefaulted,
+}
+
+/// One credit event from the dump
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreditEvent {
+    /// The user's unique ID (64 hex characters)
+    pub unique_id: String,
+    /// Circle the event belongs to (64 hex characters)
+    pub circle_id: String,
+    /// Event kind
+    pub event_type: EventType,
+    /// Round number, for payment events
+    #[serde(default)]
+    pub round: Option<u32>,
+    /// Amount in reference units (6 decimals), required for paid contributions
+    #[serde(default)]
+    pub amount: Option<i128>,
+    /// Unix timestamp (seconds)
+    pub timestamp: u64,
+}
+
+/// Furnisher details written to the header and every base segment
+#[derive(Clone, Debug)]
+pub struct Furnisher {
+    /// Identification number assigned by the bureau
+    pub id: String,
+    /// Reporter name
+    pub name: String,
+}
+
+/// Metro 2 account status
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum AccountStatus {
+    /// 11 - Current
+    Current,
+    /// 13 - Paid or closed, zero balance
+    PaidClosed,
+    /// 71 - Late
+    Late,
+    /// 78 - Seriously past due
+    SeriouslyPastDue,
+    /// 97 - Charged off
+    ChargedOff,
+}
+
+impl AccountStatus {
+    /// All statuses, in trailer order
+    pub const ALL: [AccountStatus; 5] = [
+        AccountStatus::Current,
+        AccountStatus::PaidClosed,
+        AccountStatus::Late,
+        AccountStatus::SeriouslyPastDue,
+        AccountStatus::ChargedOff,
+    ];
+
+    /// Two-digit status code
+    pub fn code(self) -> &'static str {
+        match self {
+            AccountStatus::Current => "11",
+            AccountStatus::PaidClosed => "13",
+            AccountStatus::Late => "71",
+            AccountStatus::SeriouslyPastDue => "78",
+            AccountStatus::ChargedOff => "97",
+        }
+    }
+
+    /// Whether the account is closed
+    pub fn is_closed(self) -> bool {
+        matches!(self, AccountStatus::PaidClosed | AccountStatus::ChargedOff)
+    }
+}
+
+/// A circle membership reported as one account
+#[derive(Clone, Debug)]
+pub struct Tradeline {
+    /// The user's unique ID (hex)
+    pub unique_id: String,
+    /// Circle ID (hex)
+    pub circle_id: String,
+    /// Timestamp of the first event
+    pub opened_at: u64,
+    /// Timestamp of the latest event
+    pub last_activity: u64,
+    /// Highest round seen
+    pub rounds: u32,
+    /// Total contributed (reference units)
+    pub total_paid: i128,
+    /// Most recent contribution (reference units)
+    pub last_payment: i128,
+    /// Number of paid contributions
+    pub payments: u32,
+    /// Number of missed contributions
+    pub missed: u32,
+    /// Current account status
+    pub status: AccountStatus,
+    /// Payment history profile, most recent first
+    pub history: Vec<char>,
+}
+
+impl Tradeline {
+    /// Account number reported for the tradeline
+    pub fn account_number(&self) -> String {
+        format!("{}{}", &self.unique_id[..12], &self.circle_id[..12])
+    }
+
+    /// Average contribution (reference units)
+    pub fn scheduled_payment(&self) -> i128 {
+        if self.payments == 0 {
+            0
+        } else {
+            self.total_paid / self.payments as i128
+        }
+    }
+}
+
+/// Validation failure for an event or the furnisher details
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    /// Index of the event in the dump (`None` for furnisher details)
+    pub index: Option<usize>,
+    /// Field that failed validation
+    pub field: &'static str,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "event {}: {}: {}", index, self.field, self.message),
+            None => write!(f, "furnisher: {}: {}", self.field, self.message),
+        }
+    }
+}
+
+/// Parse a JSON array of credit events.
+pub fn parse_events(json: &str) -> Result<Vec<CreditEvent>, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// Check the furnisher details.
+pub fn validate_furnisher(furnisher: &Furnisher) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let error = |field, message: &str| ValidationError {
+        index: None,
+        field,
+        message: message.to_string(),
+    };
+
+    if furnisher.id.is_empty() || furnisher.id.len() > 20 {
+        errors.push(error("id", "must be 1-20 characters"));
+    } else if !furnisher.id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        errors.push(error("id", "must be alphanumeric"));
+    }
+    if furnisher.name.trim().is_empty() {
+        errors.push(error("name", "is required"));
+    } else if !furnisher.name.is_ascii() {
+        errors.push(error("name", "must be ASCII"));
+    }
+
+    errors
+}
+
+/// Check the required fields of every event.
+pub fn validate_events(events: &[CreditEvent]) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    for (index, event) in events.iter().enumerate() {
+        let mut error = |field, message: &str| {
+            errors.push(ValidationError {
+                index: Some(index),
+                field,
+                message: message.to_string(),
+            })
+        };
+
+        if !is_hex_id(&event.unique_id) {
+            error("unique_id", "must be 64 hex characters");
+        }
+        if !is_hex_id(&event.circle_id) {
+            error("circle_id", "must be 64 hex characters");
+        }
+        if event.timestamp == 0 {
+            error("timestamp", "is required");
+        }
+
+        match event.event_type {
+            EventType::PaymentOntime | EventType::PaymentLate => {
+                match event.amount {
+                    None => error("amount", "is required for paid contributions"),
+                    Some(amount) if amount <= 0 => error("amount", "must be positive"),
+                    Some(_) => {}
+                }
+                if event.round.is_none() {
+                    error("round", "is required for payment events");
+                }
+            }
+            EventType::PaymentMissed => {
+                if event.round.is_none() {
+                    error("round", "is required for payment events");
+                }
+            }
+            EventType::CircleCompleted | EventType::CircleDefaulted => {}
+        }
+    }
+
+    errors
+}
+
+/// Group events into one tradeline per circle membership.
+/// Events are applied in timestamp order; call `validate_events` first.
+pub fn build_tradelines(events: &[CreditEvent]) -> Vec<Tradeline> {
+    let mut sorted: Vec<&CreditEvent> = events.iter().collect();
+    sorted.sort_by_key(|event| event.timestamp);
+
+    let mut tradelines: BTreeMap<(String, String), Tradeline> = BTreeMap::new();
+    for event in sorted {
+        let unique_id = event.unique_id.to_ascii_lowercase();
+        let circle_id = event.circle_id.to_ascii_lowercase();
+        let tradeline = tradelines
+            .entry((unique_id.clone(), circle_id.clone()))
+            .or_insert_with(|| Tradeline {
+                unique_id,
+                circle_id,
+                opened_at: event.timestamp,
+                last_activity: event.timestamp,
+                rounds: 0,
+                total_paid: 0,
+                last_payment: 0,
+                payments: 0,
+                missed: 0,
+                status: AccountStatus::Current,
+                history: Vec::new(),
+            });
+
+        tradeline.last_activity = event.timestamp;
+        tradeline.rounds = tradeline.rounds.max(event.round.unwrap_or(0));
+
+        // Closed accounts keep their final status
+        if tradeline.status.is_closed() {
+            continue;
+        }
+
+        let (status, history) = match event.event_type {
+            EventType::PaymentOntime => (AccountStatus::Current, Some('0')),
+            EventType::PaymentLate => (AccountStatus::Late, Some('1')),
+            EventType::PaymentMissed => (AccountStatus::SeriouslyPastDue, Some('3')),
+            EventType::CircleCompleted => (AccountStatus::PaidClosed, None),
+            EventType::CircleDefaulted => (AccountStatus::ChargedOff, Some('L')),
+        };
+
+        match event.event_type {
+            EventType::PaymentOntime | EventType::PaymentLate => {
+                let amount = event.amount.unwrap_or(0);
+                tradeline.total_paid += amount;
+                tradeline.last_payment = amount;
+                tradeline.payments += 1;
+            }
+            EventType::PaymentMissed => tradeline.missed += 1,
+            EventType::CircleCompleted | EventType::CircleDefaulted => {}
+        }
+
+        tradeline.status = status;
+        if let Some(code) = history {
+            tradeline.history.insert(0, code);
+            tradeline.history.truncate(HISTORY_LENGTH);
+        }
+    }
+
+    tradelines.into_values().collect()
+}
+
+/// Render the furnishing file for `tradelines`.
+/// The activity date is the latest activity across all tradelines.
+pub fn render_file(furnisher: &Furnisher, tradelines: &[Tradeline]) -> String {
+    let activity = tradelines
+        .iter()
+        .map(|tradeline| tradeline.last_activity)
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    out.push_str(&header_record(furnisher, activity));
+    out.push('\n');
+    for tradeline in tradelines {
+        out.push_str(&base_segment(furnisher, tradeline));
+        out.push('\n');
+    }
+    out.push_str(&trailer_record(tradelines));
+    out.push('\n');
+    out
+}
+
+/// Header record.
+pub fn header_record(furnisher: &Furnisher, activity: u64) -> String {
+    let mut record = String::new();
+    record.push_str(&numeric(RECORD_LENGTH as i128, 4));
+    record.push_str(&alpha("HEADER", 6));
+    record.push_str(&alpha(&furnisher.id, 20));
+    record.push_str(&alpha(&furnisher.name, 40));
+    record.push_str(&date(activity));
+    record.push_str(&alpha("HALO", 10));
+    finish(record)
+}
+
+/// Base segment for one tradeline.
+pub fn base_segment(furnisher: &Furnisher, tradeline: &Tradeline) -> String {
+    let past_due = if tradeline.status == AccountStatus::Current || tradeline.status.is_closed() {
+        0
+    } else {
+        tradeline.scheduled_payment() * tradeline.missed as i128
+    };
+    let closed_at = if tradeline.status.is_closed() {
+        tradeline.last_activity
+    } else {
+        0
+    };
+
+    let mut history: String = tradeline.history.iter().collect();
+    while history.len() < HISTORY_LENGTH {
+        // No history prior to this month
+        history.push('B');
+    }
+
+    let mut record = String::new();
+    record.push_str(&numeric(RECORD_LENGTH as i128, 4));
+    record.push('1'); // Processing indicator
+    record.push_str(&timestamp(tradeline.last_activity));
+    record.push_str(&alpha(&furnisher.id, 20));
+    record.push_str(&alpha(&tradeline.account_number(), 30));
+    record.push_str(PORTFOLIO_TYPE);
+    record.push_str(ACCOUNT_TYPE);
+    record.push_str(&date(tradeline.opened_at));
+    record.push_str(&numeric(dollars(tradeline.total_paid), 9));
+    record.push_str(&numeric(tradeline.rounds as i128, 3));
+    record.push_str(&numeric(dollars(tradeline.scheduled_payment()), 9));
+    record.push_str(&numeric(dollars(tradeline.last_payment), 9));
+    record.push_str(tradeline.status.code());
+    record.push_str(&history);
+    record.push_str(&numeric(0, 9)); // Current balance - contributions are paid in full
+    record.push_str(&numeric(dollars(past_due), 9));
+    record.push_str(&date(tradeline.last_activity));
+    record.push_str(&date(closed_at));
+    record.push_str(&alpha(&tradeline.unique_id, 64));
+    finish(record)
+}
+
+/// Trailer record.
+pub fn trailer_record(tradelines: &[Tradeline]) -> String {
+    let mut record = String::new();
+    record.push_str(&numeric(RECORD_LENGTH as i128, 4));
+    record.push_str(&alpha("TRAILER", 7));
+    record.push_str(&numeric(tradelines.len() as i128, 9));
+    for status in AccountStatus::ALL {
+        let count = tradelines.iter().filter(|t| t.status == status).count();
+        record.push_str(&numeric(count as i128, 9));
+    }
+    finish(record)
+}
+
+// ============ Formatting Helpers ============
+
+fn is_hex_id(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Whole dollars from reference units, rounded down
+fn dollars(amount: i128) -> i128 {
+    amount / UNITS_PER_DOLLAR
+}
+
+/// Left-aligned, space-padded alphanumeric field (truncated to `width`)
+fn alpha(value: &str, width: usize) -> String {
+    let value: String = value.chars().take(width).collect();
+    format!("{:<width$}", value.to_ascii_uppercase(), width = width)
+}
+
+/// Right-aligned, zero-filled numeric field (capped at the largest value that fits)
+fn numeric(value: i128, width: usize) -> String {
+    let max = 10i128.pow(width as u32) - 1;
+    format!("{:0width$}", value.clamp(0, max), width = width)
+}
+
+/// YYYYMMDD, or zeros for no date
+fn date(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return "0".repeat(8);
+    }
+    let (year, month, day) = civil_from_days((timestamp / 86400) as i64);
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+/// YYYYMMDDHHMMSS
+fn timestamp(timestamp: u64) -> String {
+    let seconds = timestamp % 86400;
+    format!(
+        "{}{:02}{:02}{:02}",
+        date(timestamp.max(1)),
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+fn finish(mut record: String) -> String {
+    assert!(
+        record.len() <= RECORD_LENGTH,
+        "record exceeds RECORD_LENGTH"
+    );
+    while record.len() < RECORD_LENGTH {
+        record.push(' ');
+    }
+    record
+}
+
+/// Calendar date for a count of days since 1970-01-01 (Howard Hinnant's algorithm)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const USER: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+    const CIRCLE_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const CIRCLE_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    fn event(circle_id: &str, event_type: EventType, round: u32, timestamp: u64) -> CreditEvent {
+        CreditEvent {
+            unique_id: USER.to_string(),
+            circle_id: circle_id.to_string(),
+            event_type,
+            round: Some(round),
+            amount: Some(100_000_000),
+            timestamp,
+        }
+    }
+
+    fn furnisher() -> Furnisher {
+        Furnisher {
+            id: "HALO0001".to_string(),
+            name: "Halo Protocol".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let json = format!(
+            r#"[{{"unique_id":"{}","circle_id":"{}","event_type":"payment_ontime","round":1,"amount":100000000,"timestamp":1700000000}},
+                {{"unique_id":"{}","circle_id":"{}","event_type":"circle_completed","timestamp":1700086400}}]"#,
+            USER, CIRCLE_A, USER, CIRCLE_A
+        );
+        let events = parse_events(&json).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, EventType::PaymentOntime);
+        assert_eq!(events[1].amount, None);
+        assert!(validate_events(&events).is_empty());
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let events = vec![
+            event(CIRCLE_A, EventType::PaymentOntime, 1, 1_700_000_000),
+            event(CIRCLE_A, EventType::PaymentLate, 2, 1_702_600_000),
+            event(CIRCLE_B, EventType::PaymentOntime, 1, 1_700_000_000),
+            event(CIRCLE_B, EventType::PaymentMissed, 2, 1_702_600_000),
+            event(CIRCLE_B, EventType::CircleDefaulted, 2, 1_705_200_000),
+            // Events after closing don't reopen the account
+            event(CIRCLE_B, EventType::PaymentOntime, 3, 1_707_800_000),
+        ];
+        let tradelines = build_tradelines(&events);
+
+        assert_eq!(tradelines.len(), 2);
+        assert_eq!(tradelines[0].status, AccountStatus::Late);
+        assert_eq!(tradelines[0].history, vec!['1', '0']);
+        assert_eq!(tradelines[1].status, AccountStatus::ChargedOff);
+        assert_eq!(tradelines[1].history, vec!['L', '3', '0']);
+        assert_eq!(tradelines[1].missed, 1);
+
+        let mut completed = events[..2].to_vec();
+        completed.push(event(CIRCLE_A, EventType::CircleCompleted, 2, 1_705_200_000));
+        assert_eq!(build_tradelines(&completed)[0].status, AccountStatus::PaidClosed);
+    }
+
+    #[test]
+    fn test_fixed_width_records() {
+        let events = vec![
+            event(CIRCLE_A, EventType::PaymentOntime, 1, 1_700_000_000),
+            event(CIRCLE_A, EventType::PaymentOntime, 2, 1_702_600_000),
+        ];
+        let file = render_file(&furnisher(), &build_tradelines(&events));
+        let lines: Vec<&str> = file.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() == RECORD_LENGTH));
+        assert!(lines[0].starts_with("0250HEADERHALO0001"));
+        assert!(lines[2].starts_with("0250TRAILER000000001000000001"));
+
+        let base = lines[1];
+        assert_eq!(&base[4..5], "1");
+        assert_eq!(&base[5..19], "20231215002640");
+        assert_eq!(base[39..69].trim_end(), "010101010101AAAAAAAAAAAA");
+        assert_eq!(&base[72..80], "20231114");
+        // Total paid $200 over 2 rounds, $100 scheduled and last payment
+        assert_eq!(&base[80..89], "000000200");
+        assert_eq!(&base[89..92], "002");
+        assert_eq!(&base[92..101], "000000100");
+        assert_eq!(&base[110..112], "11");
+        assert_eq!(&base[112..136], "00BBBBBBBBBBBBBBBBBBBBBB");
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut bad = event(CIRCLE_A, EventType::PaymentLate, 1, 0);
+        bad.unique_id = "xyz".to_string();
+        bad.amount = None;
+        bad.round = None;
+        let errors = validate_events(&[bad]);
+
+        let fields: Vec<&str> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["unique_id", "timestamp", "amount", "round"]);
+        assert_eq!(errors[0].to_string(), "event 0: unique_id: must be 64 hex characters");
+
+        let furnisher = Furnisher {
+            id: "HALO-1".to_string(),
+            name: " ".to_string(),
+        };
+        assert_eq!(validate_furnisher(&furnisher).len(), 2);
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_675), (2023, 11, 14));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+}
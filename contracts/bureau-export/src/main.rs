@@ -0,0 +1,108 @@
+//! Command-line entry point for the bureau export.
+//!
+//! Usage:
+//!   halo-bureau-export --furnisher-id <ID> --furnisher-name <NAME> [--skip-invalid]
+//!                      [--output <FILE>] <EVENTS.json>
+
+use halo_bureau_export::{
+    build_tradelines, parse_events, render_file, validate_events, validate_furnisher, Furnisher,
+};
+use std::collections::BTreeSet;
+use std::process::ExitCode;
+use std::{env, fs};
+
+const USAGE: &str = "usage: halo-bureau-export --furnisher-id <ID> --furnisher-name <NAME> \
+[--skip-invalid] [--output <FILE>] <EVENTS.json>";
+
+struct Args {
+    furnisher: Furnisher,
+    input: String,
+    output: Option<String>,
+    skip_invalid: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut furnisher_id = None;
+    let mut furnisher_name = None;
+    let mut output = None;
+    let mut input = None;
+    let mut skip_invalid = false;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--furnisher-id" => furnisher_id = args.next(),
+            "--furnisher-name" => furnisher_name = args.next(),
+            "--output" | "-o" => output = args.next(),
+            "--skip-invalid" => skip_invalid = true,
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}\n{}", arg, USAGE)),
+            _ => input = Some(arg),
+        }
+    }
+
+    Ok(Args {
+        furnisher: Furnisher {
+            id: furnisher_id.ok_or(USAGE)?,
+            name: furnisher_name.ok_or(USAGE)?,
+        },
+        input: input.ok_or(USAGE)?,
+        output,
+        skip_invalid,
+    })
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args()?;
+
+    let furnisher_errors = validate_furnisher(&args.furnisher);
+    if !furnisher_errors.is_empty() {
+        let messages: Vec<String> = furnisher_errors.iter().map(|e| e.to_string()).collect();
+        return Err(messages.join("\n"));
+    }
+
+    let json = fs::read_to_string(&args.input)
+        .map_err(|e| format!("failed to read {}: {}", args.input, e))?;
+    let mut events = parse_events(&json).map_err(|e| format!("invalid event dump: {}", e))?;
+
+    let errors = validate_events(&events);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        if !args.skip_invalid {
+            return Err(format!("{} validation errors", errors.len()));
+        }
+
+        let invalid: BTreeSet<usize> = errors.iter().filter_map(|e| e.index).collect();
+        eprintln!("skipping {} invalid events", invalid.len());
+        let mut index = 0;
+        events.retain(|_| {
+            index += 1;
+            !invalid.contains(&(index - 1))
+        });
+    }
+
+    let tradelines = build_tradelines(&events);
+    let file = render_file(&args.furnisher, &tradelines);
+
+    match args.output {
+        Some(path) => {
+            fs::write(&path, file).map_err(|e| format!("failed to write {}: {}", path, e))?
+        }
+        None => print!("{}", file),
+    }
+
+    eprintln!("exported {} tradelines", tradelines.len());
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! Halo Protocol Certificate Verifier
+//!
+//! Checks Halo score certificates without calling Soroban, e.g. from a relayer or a
+//! service on another chain. A certificate can be checked two ways:
+//! - For consistency: its commitment is recomputed from its fields. Anyone can build a
+//!   consistent certificate, so this only means something for a record the caller read
+//!   from the Credit contract's storage through a node it trusts
+//! - Against a Merkle proof of the certificate set, given a trusted root relayed from
+//!   `get_certificate_root`
+//!
+//! Commitments and pseudonyms use the canonical encodings from `halo-credit-core`.
+
+use halo_credit_core::{pseudonym_preimage, CertificateFields, Tier, CERTIFICATE_TREE_DEPTH};
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Score certificate, as exported from the Credit contract's ledger entry.
+/// Byte fields are hex strings and the tier is its name (e.g. `"Good"`).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Certificate {
+    /// Hash commitment to the certificate fields
+    #[serde(deserialize_with = "hex_32")]
+    pub commitment: [u8; 32],
+    /// Audience-specific pseudonym of the unique ID
+    #[serde(deserialize_with = "hex_32")]
+    pub pseudonym: [u8; 32],
+    /// Audience the certificate was issued for
+    #[serde(deserialize_with = "hex_32")]
+    pub audience: [u8; 32],
+    /// Credit score at issuance
+    pub score: u32,
+    /// Score tier at issuance
+    #[serde(deserialize_with = "tier_name")]
+    pub tier: Tier,
+    /// Score algorithm version
+    pub score_version: u32,
+    /// Ledger sequence at issuance
+    pub ledger: u32,
+    /// Timestamp of issuance
+    pub issued_at: u64,
+    /// Timestamp the certificate expires at
+    pub expires_at: u64,
+    /// Position of the commitment in the certificate Merkle tree
+    pub index: u32,
+}
+
+impl Certificate {
+    /// Fields covered by the commitment
+    pub fn fields(&self) -> CertificateFields {
+        CertificateFields {
+            pseudonym: self.pseudonym,
+            audience: self.audience,
+            score: self.score,
+            tier: self.tier,
+            score_version: self.score_version,
+            ledger: self.ledger,
+            issued_at: self.issued_at,
+            expires_at: self.expires_at,
+            index: self.index,
+        }
+    }
+}
+
+/// Reasons a certificate fails verification
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyError {
+    /// The commitment doesn't match the certificate fields
+    CommitmentMismatch,
+    /// The tier doesn't match the score
+    TierMismatch,
+    /// The certificate was issued for a different audience
+    AudienceMismatch,
+    /// The certificate has expired
+    Expired,
+    /// The Merkle proof doesn't lead to the trusted root
+    InvalidProof,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            VerifyError::CommitmentMismatch => "commitment does not match certificate fields",
+            VerifyError::TierMismatch => "tier does not match score",
+            VerifyError::AudienceMismatch => "certificate was issued for another audience",
+            VerifyError::Expired => "certificate has expired",
+            VerifyError::InvalidProof => "merkle proof does not match root",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Parse a certificate exported from the ledger as JSON.
+pub fn parse_certificate(json: &str) -> Result<Certificate, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// SHA-256 of `data`.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// Commitment for the certificate fields.
+pub fn commitment(fields: &CertificateFields) -> [u8; 32] {
+    sha256(&fields.preimage())
+}
+
+/// Pseudonym of `unique_id` for `audience`.
+pub fn pseudonym(unique_id: &[u8; 32], audience: &[u8; 32]) -> [u8; 32] {
+    sha256(&pseudonym_preimage(unique_id, audience))
+}
+
+/// Whether the certificate names `unique_id`. Only parties told the unique ID can check this.
+pub fn is_holder(certificate: &Certificate, unique_id: &[u8; 32]) -> bool {
+    pseudonym(unique_id, &certificate.audience) == certificate.pseudonym
+}
+
+/// Check that a certificate is consistent: its commitment must match its fields, it must
+/// be for `audience` and unexpired at `now` (unix seconds).
+///
+/// This proves nothing on its own, since anyone can build a certificate that passes. Use
+/// [`verify_with_proof`] unless the certificate was read from the ledger by a trusted node.
+pub fn check_consistency(
+    certificate: &Certificate,
+    audience: &[u8; 32],
+    now: u64,
+) -> Result<(), VerifyError> {
+    if commitment(&certificate.fields()) != certificate.commitment {
+        return Err(VerifyError::CommitmentMismatch);
+    }
+    if halo_credit_core::score_to_tier(certificate.score) != certificate.tier {
+        return Err(VerifyError::TierMismatch);
+    }
+    if certificate.audience != *audience {
+        return Err(VerifyError::AudienceMismatch);
+    }
+    if now >= certificate.expires_at {
+        return Err(VerifyError::Expired);
+    }
+    Ok(())
+}
+
+/// Check a certificate against a Merkle proof of the certificate set and a trusted root.
+pub fn verify_with_proof(
+    certificate: &Certificate,
+    audience: &[u8; 32],
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+    now: u64,
+) -> Result<(), VerifyError> {
+    check_consistency(certificate, audience, now)?;
+    if !verify_merkle_proof(&certificate.commitment, certificate.index, proof, root) {
+        return Err(VerifyError::InvalidProof);
+    }
+    Ok(())
+}
+
+// ============ Merkle Tree ============
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left);
+    data[32..].copy_from_slice(right);
+    sha256(&data)
+}
+
+/// Roots of empty subtrees per level (level 0 is the zero leaf)
+fn zero_hashes() -> Vec<[u8; 32]> {
+    let mut zeros = vec![[0u8; 32]];
+    for level in 0..CERTIFICATE_TREE_DEPTH as usize {
+        let zero = zeros[level];
+        zeros.push(hash_pair(&zero, &zero));
+    }
+    zeros
+}
+
+/// Root of the certificate tree holding `leaves` (commitments in index order).
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let zeros = zero_hashes();
+    let mut level_nodes = leaves.to_vec();
+    for zero in zeros.iter().take(CERTIFICATE_TREE_DEPTH as usize) {
+        level_nodes = level_nodes
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(zero)))
+            .collect();
+    }
+    level_nodes
+        .first()
+        .copied()
+        .unwrap_or(zeros[CERTIFICATE_TREE_DEPTH as usize])
+}
+
+/// Sibling hashes from the leaf at `index` up to the root.
+/// Returns `None` if `index` is out of range.
+pub fn merkle_proof(leaves: &[[u8; 32]], index: u32) -> Option<Vec<[u8; 32]>> {
+    if index as usize >= leaves.len() {
+        return None;
+    }
+
+    let zeros = zero_hashes();
+    let mut proof = Vec::with_capacity(CERTIFICATE_TREE_DEPTH as usize);
+    let mut level_nodes = leaves.to_vec();
+    let mut position = index as usize;
+    for zero in zeros.iter().take(CERTIFICATE_TREE_DEPTH as usize) {
+        proof.push(*level_nodes.get(position ^ 1).unwrap_or(zero));
+        level_nodes = level_nodes
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(zero)))
+            .collect();
+        position /= 2;
+    }
+    Some(proof)
+}
+
+/// Check that `leaf` sits at `index` in the tree with `root`.
+pub fn verify_merkle_proof(
+    leaf: &[u8; 32],
+    index: u32,
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+) -> bool {
+    if proof.len() != CERTIFICATE_TREE_DEPTH as usize {
+        return false;
+    }
+
+    let mut node = *leaf;
+    let mut position = index;
+    for sibling in proof {
+        node = if position.is_multiple_of(2) {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        };
+        position /= 2;
+    }
+    node == *root
+}
+
+// ============ Deserialization Helpers ============
+
+fn hex_32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    let value = String::deserialize(deserializer)?;
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(value, &mut out).map_err(serde::de::Error::custom)?;
+    Ok(out)
+}
+
+fn tier_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Tier, D::Error> {
+    match String::deserialize(deserializer)?.as_str() {
+        "Building" => Ok(Tier::Building),
+        "Fair" => Ok(Tier::Fair),
+        "Good" => Ok(Tier::Good),
+        "Excellent" => Ok(Tier::Excellent),
+        other => Err(serde::de::Error::custom(format!("unknown tier {}", other))),
+    }
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+    use halo_credit::{HaloCredit, HaloCreditClient, ScoreCertificate, ScoreTier};
+    use halo_identity::{HaloIdentity, HaloIdentityClient};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{Address, BytesN, Env};
+
+    const AUDIENCE: [u8; 32] = [0xaa; 32];
+
+    fn to_certificate(cert: &ScoreCertificate) -> Certificate {
+        Certificate {
+            commitment: cert.commitment.to_array(),
+            pseudonym: cert.pseudonym.to_array(),
+            audience: cert.audience.to_array(),
+            score: cert.score,
+            tier: match cert.tier {
+                ScoreTier::Building => Tier::Building,
+                ScoreTier::Fair => Tier::Fair,
+                ScoreTier::Good => Tier::Good,
+                ScoreTier::Excellent => Tier::Excellent,
+            },
+            score_version: cert.score_version,
+            ledger: cert.ledger,
+            issued_at: cert.issued_at,
+            expires_at: cert.expires_at,
+            index: cert.index,
+        }
+    }
+
+    /// Issue `count` certificates from a real Credit contract
+    fn issue(count: u8) -> (Vec<Certificate>, [u8; 32], Vec<[u8; 32]>) {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let usdc = Address::generate(&env);
+
+        let identity_id = env.register_contract(None, HaloIdentity);
+        let identity = HaloIdentityClient::new(&env, &identity_id);
+        identity.initialize(&admin);
+
+        let credit_id = env.register_contract(None, HaloCredit);
+        let credit = HaloCreditClient::new(&env, &credit_id);
+        credit.initialize(&admin);
+        credit.authorize_contract(&circle_contract);
+        credit.set_identity_contract(&identity_id);
+
+        let audience = BytesN::from_array(&env, &AUDIENCE);
+        let circle_id = BytesN::from_array(&env, &[0xcc; 32]);
+        let mut certificates = Vec::new();
+        let mut unique_ids = Vec::new();
+        for seed in 1..=count {
+            let unique_id = BytesN::from_array(&env, &[seed; 32]);
+            let wallet = Address::generate(&env);
+            identity.bind_wallet(&unique_id, &wallet);
+            credit.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &(seed % 2 == 1));
+
+            let cert = credit.issue_score_certificate(&wallet, &unique_id, &audience, &1_000);
+            certificates.push(to_certificate(&cert));
+            unique_ids.push(unique_id.to_array());
+        }
+
+        (certificates, credit.get_certificate_root().root.to_array(), unique_ids)
+    }
+
+    #[test]
+    fn test_matches_contract_commitments() {
+        let (certificates, _, unique_ids) = issue(2);
+
+        for (certificate, unique_id) in certificates.iter().zip(&unique_ids) {
+            assert_eq!(commitment(&certificate.fields()), certificate.commitment);
+            assert!(is_holder(certificate, unique_id));
+            assert_eq!(check_consistency(certificate, &AUDIENCE, 999), Ok(()));
+        }
+        assert!(!is_holder(&certificates[0], &unique_ids[1]));
+    }
+
+    #[test]
+    fn test_matches_contract_root() {
+        let (certificates, root, _) = issue(5);
+        let leaves: Vec<[u8; 32]> = certificates.iter().map(|c| c.commitment).collect();
+
+        assert_eq!(merkle_root(&leaves), root);
+        for certificate in &certificates {
+            let proof = merkle_proof(&leaves, certificate.index).unwrap();
+            assert_eq!(verify_with_proof(certificate, &AUDIENCE, &proof, &root, 0), Ok(()));
+        }
+
+        let proof = merkle_proof(&leaves, 1).unwrap();
+        assert_eq!(
+            verify_with_proof(&certificates[2], &AUDIENCE, &proof, &root, 0),
+            Err(VerifyError::InvalidProof)
+        );
+        assert!(merkle_proof(&leaves, 5).is_none());
+    }
+
+    #[test]
+    fn test_rejects_tampered_certificates() {
+        let (certificates, root, _) = issue(1);
+
+        let mut raised = certificates[0].clone();
+        raised.score += 50;
+        raised.tier = halo_credit_core::score_to_tier(raised.score);
+        assert_eq!(check_consistency(&raised, &AUDIENCE, 0), Err(VerifyError::CommitmentMismatch));
+
+        // A forged certificate with a matching commitment is only caught by the proof
+        raised.commitment = commitment(&raised.fields());
+        assert_eq!(check_consistency(&raised, &AUDIENCE, 0), Ok(()));
+        let proof = merkle_proof(&[certificates[0].commitment], 0).unwrap();
+        assert_eq!(
+            verify_with_proof(&raised, &AUDIENCE, &proof, &root, 0),
+            Err(VerifyError::InvalidProof)
+        );
+
+        assert_eq!(check_consistency(&certificates[0], &[0xbb; 32], 0), Err(VerifyError::AudienceMismatch));
+        assert_eq!(check_consistency(&certificates[0], &AUDIENCE, 1_000), Err(VerifyError::Expired));
+    }
+
+    #[test]
+    fn test_parse_exported_entry() {
+        let (certificates, _, _) = issue(1);
+        let c = &certificates[0];
+        let json = format!(
+            r#"{{"commitment":"{}","pseudonym":"{}","audience":"{}","score":{},"tier":"{:?}","score_version":{},"ledger":{},"issued_at":{},"expires_at":{},"index":{}}}"#,
+            hex::encode(c.commitment),
+            hex::encode(c.pseudonym),
+            hex::encode(c.audience),
+            c.score,
+            c.tier,
+            c.score_version,
+            c.ledger,
+            c.issued_at,
+            c.expires_at,
+            c.index
+        );
+
+        assert_eq!(parse_certificate(&json).unwrap(), *c);
+        assert!(parse_certificate(&json.replace("\"score\"", "\"scor\"")).is_err());
+    }
+
+    #[test]
+    fn test_empty_tree_root() {
+        let zeros = zero_hashes();
+        assert_eq!(merkle_root(&[]), zeros[CERTIFICATE_TREE_DEPTH as usize]);
+    }
+}
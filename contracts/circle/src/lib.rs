@@ -8,6 +8,9 @@
 //! - Circle creation with configurable parameters
 //! - Member management with identity verification
 //! - Contribution tracking (on-time, late, missed)
+//! - Security deposits escrowed on join, slashed on default
+//! - Rotation or chit-fund style auction payouts (open or sealed bids)
+//! - Payout order by join order, PRNG shuffle, credit score or member vote
 //! - Automatic payout processing
 //! - Integration with Identity and Credit contracts
 //!
@@ -15,14 +18,24 @@
 //! 1. Forming - Accepting members until full
 //! 2. Active - Contributions and payouts in progress
 //! 3. Completed - All rounds finished
+//!
+//! A circle still forming at its deadline starts with the members it has if it
+//! reached its minimum, and is cancelled with deposits refunded otherwise.
+//!
+//! A round that is still short after its grace period can be settled by anyone
+//! with `settle_round`, which reports the missed payments and applies the
+//! circle's default policy.
 
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN,
-    Env, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Bytes, BytesN, Env, String, Symbol, Vec,
 };
 
+/// Deposit discount in percent by credit tier (Building, Fair, Good, Excellent)
+const DEPOSIT_TIER_DISCOUNTS: [u32; 4] = [0, 10, 25, 50];
+
 // Cross-contract client imports
 // These are generated from the Identity and Credit contract WASM files
 #[cfg(feature = "cross-contract")]
@@ -39,6 +52,13 @@ mod credit_client {
     );
 }
 
+/// Subset of the Credit contract interface used to order payouts by credit score
+#[allow(dead_code)]
+#[contractclient(name = "CreditScoreClient")]
+trait CreditScoreInterface {
+    fn get_score(env: Env, unique_id: BytesN<32>) -> Option<u32>;
+}
+
 /// Storage keys for the contract
 #[derive(Clone)]
 #[contracttype]
@@ -57,6 +77,16 @@ pub enum DataKey {
     InviteCode(BytesN<16>),
     /// Total circles created
     CircleCount,
+    /// Auction bid for a circle round by member
+    Bid(BytesN<32>, u32, Address),
+    /// Need-based payout request for a circle round by member
+    NeedRequest(BytesN<32>, u32, Address),
+    /// Need vote cast in a circle round by voter
+    NeedVote(BytesN<32>, u32, Address),
+    /// Open payout position swap proposal by proposer
+    SwapProposal(BytesN<32>, Address),
+    /// Payout position listed for sale by seller
+    Listing(BytesN<32>, Address),
 }
 
 /// Contract errors
@@ -100,6 +130,38 @@ pub enum CircleError {
     InvalidInviteCode = 17,
     /// Circle already started
     CircleAlreadyStarted = 18,
+    /// Round cannot be settled until its grace period has ended
+    GracePeriodActive = 19,
+    /// Operation not available in this circle's payout mode
+    WrongPayoutMode = 20,
+    /// Bidding or voting window for this round has closed
+    BiddingClosed = 21,
+    /// Bidding or voting for this round has not closed yet
+    BiddingOpen = 22,
+    /// Bid out of range or not above the leading bid
+    InvalidBid = 23,
+    /// No bid to reveal
+    BidNotFound = 24,
+    /// Revealed bid does not match its commitment
+    RevealMismatch = 25,
+    /// Member has no need request this round
+    RequestNotFound = 26,
+    /// Member already voted this round
+    AlreadyVoted = 27,
+    /// Members cannot vote for their own request
+    InvalidVote = 28,
+    /// No open swap proposal
+    SwapNotFound = 29,
+    /// Positions are not both future, unpaid and held by the swapping members
+    InvalidSwap = 30,
+    /// No listing for this seller
+    ListingNotFound = 31,
+    /// Position cannot be sold (paid, current round, missed payments or creator)
+    PositionNotTransferable = 32,
+    /// Forming deadline not set or not yet passed
+    DeadlineNotReached = 33,
+    /// Forming deadline has passed
+    FormingDeadlinePassed = 34,
 }
 
 /// Circle status
@@ -114,6 +176,58 @@ pub enum CircleStatus {
     Completed,
     /// Circle was cancelled
     Cancelled,
+    /// A round was settled with missed payments under the `Pause` policy
+    Paused,
+}
+
+/// Security deposit a member escrows when joining
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DepositRequirement {
+    /// No deposit
+    None,
+    /// Fixed amount in the contribution token
+    Fixed(i128),
+    /// Multiple of `contribution_amount`
+    Multiple(u32),
+}
+
+/// How payout positions are assigned when the circle starts
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PayoutOrder {
+    /// Positions follow join order
+    JoinOrder,
+    /// Positions are shuffled with the ledger PRNG
+    Random,
+    /// Highest credit scores first, read from the Credit contract at start
+    CreditScore,
+    /// Each round goes to the member whose need request gets the most votes
+    NeedVote,
+}
+
+/// How each round's recipient is chosen
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PayoutMode {
+    /// Members are paid in payout order
+    Rotation,
+    /// Members bid the discount they accept; bids are public and must beat the leader
+    OpenAuction,
+    /// Members commit to a hidden discount, then reveal it after the bidding window
+    SealedAuction,
+}
+
+/// What `settle_round` does when members miss a contribution
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DefaultPolicy {
+    /// Pay the recipient whatever was collected
+    PayoutCollected,
+    /// Top the payout up from the circle's reserve, then pay what is available
+    CoverShortfall,
+    /// Hold the round until the missing contributions arrive
+    Pause,
 }
 
 /// Circle configuration
@@ -134,6 +248,25 @@ pub struct CircleConfig {
     pub grace_period: u64,
     /// Late fee percentage (0-100)
     pub late_fee_percent: u32,
+    /// Pay out in the same transaction as the final contribution of a round.
+    /// When off, the creator triggers each payout with `process_payout`.
+    pub auto_payout: bool,
+    /// Policy applied when a round is settled with missed payments
+    pub default_policy: DefaultPolicy,
+    /// Security deposit required to join, discounted for higher credit tiers
+    pub deposit: DepositRequirement,
+    /// How payout positions are assigned when the circle starts
+    pub payout_order: PayoutOrder,
+    /// How each round's recipient is chosen
+    pub payout_mode: PayoutMode,
+    /// Length of each round's bidding or need-voting window in seconds.
+    /// Sealed auctions are followed by a reveal window of the same length.
+    pub bid_window: u64,
+    /// Timestamp after which a forming circle can be expired (0 for no deadline)
+    pub forming_deadline: u64,
+    /// Members needed to start with a smaller group at the deadline (3 or more).
+    /// Ignored when there is no deadline.
+    pub min_members: u32,
 }
 
 /// Circle state
@@ -162,6 +295,10 @@ pub struct CircleState {
     pub total_paid_out: i128,
     /// Invite code for joining
     pub invite_code: BytesN<16>,
+    /// Late fees held to cover shortfalls, shared among the members at completion
+    pub reserve: i128,
+    /// Seconds the round schedule has been pushed back by pauses
+    pub pause_offset: u64,
 }
 
 /// Member state within a circle
@@ -180,6 +317,10 @@ pub struct MemberState {
     pub has_received_payout: bool,
     /// Rounds contributed to (for tracking)
     pub rounds_contributed: Vec<u32>,
+    /// Rounds settled without their contribution
+    pub rounds_missed: Vec<u32>,
+    /// Security deposit currently held in escrow
+    pub deposit: i128,
 }
 
 /// Contribution record
@@ -210,6 +351,92 @@ pub struct PayoutRecord {
     pub round: u32,
     /// Amount paid out
     pub amount: i128,
+    /// Auction discount given up by the recipient and paid to the other members
+    pub discount: i128,
+    /// Timestamp
+    pub timestamp: u64,
+}
+
+/// Proposed exchange of two future payout positions
+#[derive(Clone)]
+#[contracttype]
+pub struct SwapProposal {
+    /// Member proposing the swap
+    pub proposer: Address,
+    /// Member holding the requested position
+    pub counterparty: Address,
+    /// Proposer's current payout position
+    pub proposer_position: u32,
+    /// Position the proposer wants
+    pub counterparty_position: u32,
+    /// Timestamp
+    pub created_at: u64,
+}
+
+/// Payout position offered for sale
+#[derive(Clone)]
+#[contracttype]
+pub struct PositionListing {
+    /// Member selling their slot
+    pub seller: Address,
+    /// Payout position being sold
+    pub position: u32,
+    /// Price in the circle's contribution token
+    pub price: i128,
+    /// Timestamp
+    pub created_at: u64,
+}
+
+/// Need-based payout request for a round
+#[derive(Clone)]
+#[contracttype]
+pub struct NeedRequest {
+    /// Requesting member
+    pub member: Address,
+    /// Round number
+    pub round: u32,
+    /// Why the member needs this round's payout
+    pub reason: String,
+    /// Votes received
+    pub votes: u32,
+    /// Timestamp
+    pub timestamp: u64,
+}
+
+/// Auction bid for a round
+#[derive(Clone)]
+#[contracttype]
+pub struct Bid {
+    /// Bidding member
+    pub bidder: Address,
+    /// Round number
+    pub round: u32,
+    /// Discount on the pot the bidder accepts (valid once revealed)
+    pub discount: i128,
+    /// Sealed bid commitment: sha256(discount as 16 big-endian bytes || salt)
+    pub commitment: Option<BytesN<32>>,
+    /// Whether the discount is known (always true for open bids)
+    pub revealed: bool,
+    /// Timestamp
+    pub timestamp: u64,
+}
+
+/// Outcome of settling a round after its grace period
+#[derive(Clone)]
+#[contracttype]
+pub struct RoundSettlement {
+    /// Round number
+    pub round: u32,
+    /// Members who did not contribute
+    pub missed: Vec<Address>,
+    /// Contributions received for the round
+    pub collected: i128,
+    /// Amount taken from defaulters' deposits
+    pub slashed: i128,
+    /// Amount taken from the reserve to cover the shortfall
+    pub covered: i128,
+    /// Amount paid to the recipient (0 if the circle paused)
+    pub paid_out: i128,
     /// Timestamp
     pub timestamp: u64,
 }
@@ -269,9 +496,9 @@ impl HaloCircle {
 
         // Validate configuration
         Self::validate_config(&config)?;
-
-        // Verify creator has bound identity
-        Self::verify_identity(&env, &creator)?;
+        if config.forming_deadline != 0 && config.forming_deadline <= env.ledger().timestamp() {
+            return Err(CircleError::InvalidConfig);
+        }
 
         // Generate circle ID
         let count: u64 = env
@@ -284,9 +511,6 @@ impl HaloCircle {
         // Generate invite code
         let invite_code = Self::generate_invite_code(&env, &circle_id);
 
-        // Get creator's unique ID
-        let unique_id = Self::get_unique_id(&env, &creator)?;
-
         // Create circle state
         let current_time = env.ledger().timestamp();
         let state = CircleState {
@@ -301,6 +525,8 @@ impl HaloCircle {
             total_contributed: 0,
             total_paid_out: 0,
             invite_code: invite_code.clone(),
+            reserve: 0,
+            pause_offset: 0,
         };
 
         // Store circle
@@ -318,8 +544,8 @@ impl HaloCircle {
             .instance()
             .set(&DataKey::CircleCount, &(count + 1));
 
-        // Auto-join creator as first member
-        Self::internal_join(&env, &circle_id, &creator, &unique_id)?;
+        // Auto-join creator as first member (this verifies their identity)
+        Self::internal_join(&env, &circle_id, &creator)?;
 
         // Extend TTL
         env.storage().persistent().extend_ttl(
@@ -353,12 +579,8 @@ impl HaloCircle {
             .get(&DataKey::InviteCode(invite_code))
             .ok_or(CircleError::InvalidInviteCode)?;
 
-        // Verify member has bound identity
-        Self::verify_identity(&env, &member)?;
-        let unique_id = Self::get_unique_id(&env, &member)?;
-
         // Join the circle
-        let position = Self::internal_join(&env, &circle_id, &member, &unique_id)?;
+        let position = Self::internal_join(&env, &circle_id, &member)?;
 
         env.events().publish(
             (Symbol::new(&env, "member_joined"), symbol_short!("join")),
@@ -376,10 +598,7 @@ impl HaloCircle {
     ) -> Result<u32, CircleError> {
         member.require_auth();
 
-        Self::verify_identity(&env, &member)?;
-        let unique_id = Self::get_unique_id(&env, &member)?;
-
-        let position = Self::internal_join(&env, &circle_id, &member, &unique_id)?;
+        let position = Self::internal_join(&env, &circle_id, &member)?;
 
         env.events().publish(
             (Symbol::new(&env, "member_joined"), symbol_short!("join")),
@@ -389,6 +608,59 @@ impl HaloCircle {
         Ok(position)
     }
 
+    /// Leave a forming circle. The creator cancels the circle instead.
+    pub fn leave_circle(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+    ) -> Result<(), CircleError> {
+        member.require_auth();
+
+        let mut state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        if member == state.creator {
+            return Err(CircleError::Unauthorized);
+        }
+        Self::remove_forming_member(&env, &circle_id, &mut state, &member)?;
+
+        env.events()
+            .publish((Symbol::new(&env, "member_left"),), (circle_id, member));
+
+        Ok(())
+    }
+
+    /// Remove a member from a forming circle. Only callable by the circle creator.
+    pub fn remove_member(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+        reason: String,
+    ) -> Result<(), CircleError> {
+        let mut state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        state.creator.require_auth();
+
+        if member == state.creator {
+            return Err(CircleError::Unauthorized);
+        }
+        Self::remove_forming_member(&env, &circle_id, &mut state, &member)?;
+
+        env.events().publish(
+            (Symbol::new(&env, "member_removed"),),
+            (circle_id, member, reason),
+        );
+
+        Ok(())
+    }
+
     // ============ Contributions ============
 
     /// Make a contribution for the current round.
@@ -405,8 +677,8 @@ impl HaloCircle {
             .get(&DataKey::Circle(circle_id.clone()))
             .ok_or(CircleError::CircleNotFound)?;
 
-        // Verify circle is active
-        if state.status != CircleStatus::Active {
+        // Verify circle is active (a paused round still accepts late contributions)
+        if state.status != CircleStatus::Active && state.status != CircleStatus::Paused {
             return Err(CircleError::CircleNotActive);
         }
 
@@ -424,7 +696,7 @@ impl HaloCircle {
 
         // Calculate timing
         let current_time = env.ledger().timestamp();
-        let round_start = state.started_at + ((state.current_round as u64 - 1) * state.config.period_length);
+        let round_start = Self::round_start(&state);
         let due_date = round_start + state.config.period_length;
         let grace_end = due_date + state.config.grace_period;
 
@@ -450,6 +722,7 @@ impl HaloCircle {
 
         // Update circle state
         state.total_contributed += amount;
+        state.reserve += late_fee;
 
         // Store updates
         env.storage().persistent().set(
@@ -460,15 +733,18 @@ impl HaloCircle {
             .persistent()
             .set(&DataKey::Circle(circle_id.clone()), &state);
 
-        // Record payment in credit contract
-        Self::record_payment_to_credit(
-            &env,
-            &member_state.unique_id,
-            &circle_id,
-            state.current_round,
-            state.config.contribution_amount,
-            !is_late,
-        );
+        // Record payment in credit contract, unless the round was already reported missed
+        if !member_state.rounds_missed.contains(state.current_round) {
+            Self::record_payment_to_credit(
+                &env,
+                &member_state.unique_id,
+                &circle_id,
+                state.current_round,
+                &state.config.contribution_token,
+                state.config.contribution_amount,
+                !is_late,
+            );
+        }
 
         let record = ContributionRecord {
             member: member.clone(),
@@ -484,7 +760,7 @@ impl HaloCircle {
             (circle_id.clone(), member, state.current_round, amount, !is_late),
         );
 
-        // Check if all contributions received, process payout if so
+        // Pay out in this transaction if this was the last contribution
         Self::try_process_payout(&env, circle_id)?;
 
         Ok(record)
@@ -492,127 +768,760 @@ impl HaloCircle {
 
     // ============ Payouts ============
 
-    /// Process payout for current round (called automatically or manually).
+    /// Process payout for the current round. Circles with auto payout enabled pay out
+    /// with the final contribution; otherwise the creator calls this.
     pub fn process_payout(env: Env, circle_id: BytesN<32>) -> Result<PayoutRecord, CircleError> {
+        let state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        if !state.config.auto_payout {
+            state.creator.require_auth();
+        }
+
+        Self::execute_payout(&env, circle_id, state)
+    }
+
+    /// Settle the current round once its grace period has ended. Members who did not
+    /// contribute are marked as missed and reported to the Credit contract, then the
+    /// circle's default policy decides the payout. Callable by anyone.
+    ///
+    /// A paused circle can be settled again after a further grace period. Deposits and
+    /// the reserve then cover what they can and the rest of the pot is paid out.
+    pub fn settle_round(env: Env, circle_id: BytesN<32>) -> Result<RoundSettlement, CircleError> {
         let mut state: CircleState = env
             .storage()
             .persistent()
             .get(&DataKey::Circle(circle_id.clone()))
             .ok_or(CircleError::CircleNotFound)?;
 
-        if state.status != CircleStatus::Active {
+        let paused = state.status == CircleStatus::Paused;
+        if state.status != CircleStatus::Active && !paused {
             return Err(CircleError::CircleNotActive);
         }
 
-        // Count contributions for this round
-        let contributions = Self::count_contributions(&env, &circle_id, &state);
+        let current_time = env.ledger().timestamp();
+        let round = state.current_round;
+        let due_date = Self::round_start(&state) + state.config.period_length;
+        let mut grace_end = due_date + state.config.grace_period;
+        if paused {
+            grace_end += state.config.grace_period;
+        }
+        if current_time <= grace_end {
+            return Err(CircleError::GracePeriodActive);
+        }
 
-        // All members must have contributed
-        if contributions < state.config.total_members {
-            return Err(CircleError::ContributionsIncomplete);
+        // Mark and report everyone who did not contribute this round
+        let mut missed = Vec::new(&env);
+        for member in state.members.iter() {
+            let key = DataKey::Member(circle_id.clone(), member.clone());
+            let mut member_state: MemberState = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .ok_or(CircleError::NotMember)?;
+
+            if member_state.rounds_contributed.contains(round) {
+                continue;
+            }
+
+            // Members were already marked when the circle paused
+            if !member_state.rounds_missed.contains(round) {
+                member_state.rounds_missed.push_back(round);
+                env.storage().persistent().set(&key, &member_state);
+
+                Self::record_missed_payment_to_credit(
+                    &env,
+                    &member_state.unique_id,
+                    &circle_id,
+                    round,
+                );
+
+                env.events().publish(
+                    (Symbol::new(&env, "contribution"), symbol_short!("missed")),
+                    (circle_id.clone(), member.clone(), round),
+                );
+            }
+            missed.push_back(member);
         }
 
-        // Get recipient (member at current round position)
-        let recipient = state
-            .members
-            .get(state.current_round - 1)
-            .ok_or(CircleError::InvalidRound)?;
+        let amount = state.config.contribution_amount;
+        let contributors = state.config.total_members - missed.len();
+        let collected = amount * contributors as i128;
+        let mut shortfall = amount * missed.len() as i128;
+
+        // Deposits cover missed contributions first; a circle about to pause keeps
+        // them unless they cover the whole shortfall
+        let policy = if paused {
+            DefaultPolicy::CoverShortfall
+        } else {
+            state.config.default_policy
+        };
+        let mut slashable: i128 = 0;
+        for member in missed.iter() {
+            let member_state = Self::load_member(&env, &circle_id, &member)?;
+            slashable += member_state.deposit.min(amount);
+        }
 
-        // Calculate payout amount
-        let payout_amount = state.config.contribution_amount * state.config.total_members as i128;
+        let mut slashed: i128 = 0;
+        if policy != DefaultPolicy::Pause || slashable >= shortfall {
+            for member in missed.iter() {
+                let key = DataKey::Member(circle_id.clone(), member.clone());
+                let mut member_state = Self::load_member(&env, &circle_id, &member)?;
+                let slash = member_state.deposit.min(amount);
+                if slash == 0 {
+                    continue;
+                }
 
-        // Transfer payout
-        let token = token::Client::new(&env, &state.config.contribution_token);
-        token.transfer(&env.current_contract_address(), &recipient, &payout_amount);
+                member_state.deposit -= slash;
+                env.storage().persistent().set(&key, &member_state);
+                slashed += slash;
 
-        // Update member state
-        let mut recipient_state: MemberState = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Member(circle_id.clone(), recipient.clone()))
-            .unwrap();
-        recipient_state.has_received_payout = true;
-        env.storage().persistent().set(
-            &DataKey::Member(circle_id.clone(), recipient.clone()),
-            &recipient_state,
+                env.events().publish(
+                    (Symbol::new(&env, "deposit_slashed"),),
+                    (circle_id.clone(), member, round, slash),
+                );
+            }
+            shortfall -= slashed;
+        }
+
+        let mut covered: i128 = 0;
+        if shortfall > 0 {
+            match policy {
+                DefaultPolicy::PayoutCollected => {}
+                DefaultPolicy::CoverShortfall => {
+                    covered = shortfall.min(state.reserve);
+                    state.reserve -= covered;
+                }
+                DefaultPolicy::Pause => {
+                    state.status = CircleStatus::Paused;
+                    env.storage()
+                        .persistent()
+                        .set(&DataKey::Circle(circle_id.clone()), &state);
+
+                    env.events().publish(
+                        (Symbol::new(&env, "circle_paused"),),
+                        (circle_id.clone(), round, missed.len()),
+                    );
+
+                    return Ok(RoundSettlement {
+                        round,
+                        missed,
+                        collected,
+                        slashed,
+                        covered,
+                        paid_out: 0,
+                        timestamp: current_time,
+                    });
+                }
+            }
+        }
+
+        let payout = Self::pay_recipient(
+            &env,
+            circle_id.clone(),
+            state,
+            collected + slashed + covered,
+        )?;
+
+        env.events().publish(
+            (Symbol::new(&env, "round_settled"),),
+            (circle_id, round, missed.len(), payout.amount),
         );
 
-        // Update circle state
-        state.total_paid_out += payout_amount;
-        state.current_round += 1;
+        Ok(RoundSettlement {
+            round,
+            missed,
+            collected,
+            slashed,
+            covered,
+            paid_out: payout.amount,
+            timestamp: current_time,
+        })
+    }
 
-        // Check if circle is complete
-        if state.current_round > state.config.total_members {
-            state.status = CircleStatus::Completed;
-            Self::finalize_circle(&env, &circle_id, &state)?;
-        }
+    // ============ Auctions ============
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Circle(circle_id.clone()), &state);
+    /// Place an open bid for the current round. The discount must beat the leading bid.
+    pub fn place_bid(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+        discount: i128,
+    ) -> Result<Bid, CircleError> {
+        member.require_auth();
 
+        let state = Self::load_bidding_circle(&env, &circle_id, PayoutMode::OpenAuction)?;
         let current_time = env.ledger().timestamp();
-        let record = PayoutRecord {
-            recipient: recipient.clone(),
-            round: state.current_round - 1,
-            amount: payout_amount,
+        if current_time >= Self::round_start(&state) + state.config.bid_window {
+            return Err(CircleError::BiddingClosed);
+        }
+        Self::check_bidder(&env, &circle_id, &member)?;
+        Self::check_discount(&state, discount)?;
+
+        if let Some(leader) = Self::leading_bid(&env, &circle_id, &state) {
+            if discount <= leader.discount {
+                return Err(CircleError::InvalidBid);
+            }
+        }
+
+        let bid = Bid {
+            bidder: member.clone(),
+            round: state.current_round,
+            discount,
+            commitment: None,
+            revealed: true,
             timestamp: current_time,
         };
+        Self::store_bid(&env, &circle_id, &bid);
 
         env.events().publish(
-            (Symbol::new(&env, "payout"), symbol_short!("out")),
-            (circle_id, recipient, state.current_round - 1, payout_amount),
+            (Symbol::new(&env, "bid"), symbol_short!("open")),
+            (circle_id, member, state.current_round, discount),
         );
 
-        Ok(record)
+        Ok(bid)
     }
 
-    // ============ Query Functions ============
+    /// Commit to a sealed bid for the current round. A later commitment replaces an earlier one.
+    pub fn commit_bid(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+        commitment: BytesN<32>,
+    ) -> Result<Bid, CircleError> {
+        member.require_auth();
 
-    /// Get circle state.
-    pub fn get_circle(env: Env, circle_id: BytesN<32>) -> Option<CircleState> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Circle(circle_id))
-    }
+        let state = Self::load_bidding_circle(&env, &circle_id, PayoutMode::SealedAuction)?;
+        let current_time = env.ledger().timestamp();
+        if current_time >= Self::round_start(&state) + state.config.bid_window {
+            return Err(CircleError::BiddingClosed);
+        }
+        Self::check_bidder(&env, &circle_id, &member)?;
 
-    /// Get circle by invite code.
-    pub fn get_circle_by_invite(env: Env, invite_code: BytesN<16>) -> Option<CircleState> {
-        let circle_id: Option<BytesN<32>> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::InviteCode(invite_code));
+        let bid = Bid {
+            bidder: member.clone(),
+            round: state.current_round,
+            discount: 0,
+            commitment: Some(commitment),
+            revealed: false,
+            timestamp: current_time,
+        };
+        Self::store_bid(&env, &circle_id, &bid);
 
-        circle_id.and_then(|id| Self::get_circle(env.clone(), id))
+        env.events().publish(
+            (Symbol::new(&env, "bid"), symbol_short!("commit")),
+            (circle_id, member, state.current_round),
+        );
+
+        Ok(bid)
     }
 
-    /// Get member state.
-    pub fn get_member(
+    /// Reveal a sealed bid during the reveal window that follows bidding.
+    pub fn reveal_bid(
         env: Env,
         circle_id: BytesN<32>,
         member: Address,
-    ) -> Option<MemberState> {
-        env.storage()
+        discount: i128,
+        salt: BytesN<32>,
+    ) -> Result<Bid, CircleError> {
+        member.require_auth();
+
+        let state = Self::load_bidding_circle(&env, &circle_id, PayoutMode::SealedAuction)?;
+        let current_time = env.ledger().timestamp();
+        let reveal_start = Self::round_start(&state) + state.config.bid_window;
+        if current_time < reveal_start {
+            return Err(CircleError::BiddingOpen);
+        }
+        if current_time >= reveal_start + state.config.bid_window {
+            return Err(CircleError::BiddingClosed);
+        }
+
+        let key = DataKey::Bid(circle_id.clone(), state.current_round, member.clone());
+        let mut bid: Bid = env
+            .storage()
             .persistent()
-            .get(&DataKey::Member(circle_id, member))
+            .get(&key)
+            .ok_or(CircleError::BidNotFound)?;
+
+        let mut preimage = Bytes::from_array(&env, &discount.to_be_bytes());
+        preimage.append(&Bytes::from_array(&env, &salt.to_array()));
+        let hash = BytesN::from_array(&env, &env.crypto().sha256(&preimage).to_array());
+        if bid.commitment != Some(hash) {
+            return Err(CircleError::RevealMismatch);
+        }
+        Self::check_discount(&state, discount)?;
+
+        bid.discount = discount;
+        bid.revealed = true;
+        env.storage().persistent().set(&key, &bid);
+
+        env.events().publish(
+            (Symbol::new(&env, "bid"), symbol_short!("reveal")),
+            (circle_id, member, state.current_round, discount),
+        );
+
+        Ok(bid)
     }
 
-    /// Check if address is a member of circle.
-    pub fn is_member(env: Env, circle_id: BytesN<32>, address: Address) -> bool {
+    /// Get a member's bid for a round.
+    pub fn get_bid(env: Env, circle_id: BytesN<32>, round: u32, member: Address) -> Option<Bid> {
         env.storage()
             .persistent()
-            .has(&DataKey::Member(circle_id, address))
+            .get(&DataKey::Bid(circle_id, round, member))
     }
 
-    /// Get contribution status for current round.
-    pub fn get_contribution_status(
+    /// Get the highest revealed bid for the current round.
+    pub fn get_leading_bid(env: Env, circle_id: BytesN<32>) -> Option<Bid> {
+        let state = Self::get_circle(env.clone(), circle_id.clone())?;
+        Self::leading_bid(&env, &circle_id, &state)
+    }
+
+    // ============ Position Swaps ============
+
+    /// Propose exchanging payout positions with the member at `their_position`.
+    /// Replaces any earlier proposal from the same member.
+    pub fn propose_swap(
         env: Env,
         circle_id: BytesN<32>,
-    ) -> (u32, u32) {
-        let state: Option<CircleState> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Circle(circle_id.clone()));
+        member: Address,
+        my_position: u32,
+        their_position: u32,
+    ) -> Result<SwapProposal, CircleError> {
+        member.require_auth();
+
+        let state = Self::load_swap_circle(&env, &circle_id)?;
+        let counterparty = Self::check_swap_position(&env, &circle_id, &state, their_position)?;
+        if Self::check_swap_position(&env, &circle_id, &state, my_position)? != member
+            || counterparty == member
+        {
+            return Err(CircleError::InvalidSwap);
+        }
+
+        let proposal = SwapProposal {
+            proposer: member.clone(),
+            counterparty: counterparty.clone(),
+            proposer_position: my_position,
+            counterparty_position: their_position,
+            created_at: env.ledger().timestamp(),
+        };
+        let key = DataKey::SwapProposal(circle_id.clone(), member.clone());
+        env.storage().persistent().set(&key, &proposal);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events().publish(
+            (Symbol::new(&env, "swap_proposed"),),
+            (circle_id, member, counterparty, my_position, their_position),
+        );
+
+        Ok(proposal)
+    }
+
+    /// Accept a swap proposed to `member` by `proposer`. Both positions must still be
+    /// future, unpaid and held by the same members as when proposed.
+    pub fn accept_swap(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+        proposer: Address,
+    ) -> Result<(), CircleError> {
+        member.require_auth();
+
+        let mut state = Self::load_swap_circle(&env, &circle_id)?;
+        let key = DataKey::SwapProposal(circle_id.clone(), proposer.clone());
+        let proposal: SwapProposal = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(CircleError::SwapNotFound)?;
+
+        if proposal.counterparty != member {
+            return Err(CircleError::SwapNotFound);
+        }
+
+        let at_proposer =
+            Self::check_swap_position(&env, &circle_id, &state, proposal.proposer_position)?;
+        let at_counterparty =
+            Self::check_swap_position(&env, &circle_id, &state, proposal.counterparty_position)?;
+        if at_proposer != proposer || at_counterparty != member {
+            return Err(CircleError::InvalidSwap);
+        }
+
+        Self::swap_positions(
+            &env,
+            &circle_id,
+            &mut state,
+            proposal.proposer_position - 1,
+            proposal.counterparty_position - 1,
+        )?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id.clone()), &state);
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (Symbol::new(&env, "swap_accepted"),),
+            (
+                circle_id,
+                proposer,
+                member,
+                proposal.counterparty_position,
+                proposal.proposer_position,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the member's open swap proposal.
+    pub fn cancel_swap(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+    ) -> Result<(), CircleError> {
+        member.require_auth();
+
+        let key = DataKey::SwapProposal(circle_id.clone(), member.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(CircleError::SwapNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((Symbol::new(&env, "swap_cancelled"),), (circle_id, member));
+
+        Ok(())
+    }
+
+    /// Get a member's open swap proposal.
+    pub fn get_swap_proposal(
+        env: Env,
+        circle_id: BytesN<32>,
+        proposer: Address,
+    ) -> Option<SwapProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SwapProposal(circle_id, proposer))
+    }
+
+    // ============ Position Market ============
+
+    /// List the member's future payout position for sale. The buyer takes over the slot
+    /// and its remaining contributions; the seller is paid and leaves the circle.
+    pub fn list_position(
+        env: Env,
+        circle_id: BytesN<32>,
+        seller: Address,
+        price: i128,
+    ) -> Result<PositionListing, CircleError> {
+        seller.require_auth();
+
+        let state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        if price < 0 {
+            return Err(CircleError::InvalidConfig);
+        }
+        let position = Self::check_sellable(&env, &circle_id, &state, &seller)?;
+
+        let listing = PositionListing {
+            seller: seller.clone(),
+            position,
+            price,
+            created_at: env.ledger().timestamp(),
+        };
+        let key = DataKey::Listing(circle_id.clone(), seller.clone());
+        env.storage().persistent().set(&key, &listing);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events().publish(
+            (Symbol::new(&env, "position_listed"),),
+            (circle_id, seller, position, price),
+        );
+
+        Ok(listing)
+    }
+
+    /// Withdraw the member's listing.
+    pub fn cancel_listing(
+        env: Env,
+        circle_id: BytesN<32>,
+        seller: Address,
+    ) -> Result<(), CircleError> {
+        seller.require_auth();
+
+        let key = DataKey::Listing(circle_id.clone(), seller.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(CircleError::ListingNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (Symbol::new(&env, "listing_cancelled"),),
+            (circle_id, seller),
+        );
+
+        Ok(())
+    }
+
+    /// Buy a listed position. The buyer must be identity-bound and not already a member;
+    /// they pay the seller, escrow their own deposit and take over the slot.
+    pub fn buy_position(
+        env: Env,
+        circle_id: BytesN<32>,
+        buyer: Address,
+        seller: Address,
+    ) -> Result<u32, CircleError> {
+        buyer.require_auth();
+
+        let mut state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        let listing_key = DataKey::Listing(circle_id.clone(), seller.clone());
+        let listing: PositionListing = env
+            .storage()
+            .persistent()
+            .get(&listing_key)
+            .ok_or(CircleError::ListingNotFound)?;
+
+        // The listing must still describe the seller's slot
+        if Self::check_sellable(&env, &circle_id, &state, &seller)? != listing.position {
+            return Err(CircleError::PositionNotTransferable);
+        }
+
+        let unique_id = Self::check_eligible(&env, &state, &buyer)?;
+
+        // Pay the seller and settle deposits
+        let token = token::Client::new(&env, &state.config.contribution_token);
+        if listing.price > 0 {
+            token.transfer(&buyer, &seller, &listing.price);
+        }
+        let deposit = Self::escrow_deposit(&env, &circle_id, &state.config, &buyer, &unique_id);
+
+        let seller_key = DataKey::Member(circle_id.clone(), seller.clone());
+        let seller_state = Self::load_member(&env, &circle_id, &seller)?;
+        let refund = seller_state.deposit;
+        if refund > 0 {
+            token.transfer(&env.current_contract_address(), &seller, &refund);
+        }
+
+        // The buyer starts a fresh record in the slot. The seller's payments were
+        // reported against their own ID as they were made; only a contribution to the
+        // current round stays with the slot, since it is already part of this pot.
+        let round = state.current_round;
+        let mut rounds_contributed = Vec::new(&env);
+        if seller_state.rounds_contributed.contains(round) {
+            rounds_contributed.push_back(round);
+        }
+        let member_state = MemberState {
+            unique_id,
+            payout_position: listing.position,
+            joined_at: env.ledger().timestamp(),
+            total_contributed: 0,
+            has_received_payout: false,
+            rounds_contributed,
+            rounds_missed: Vec::new(&env),
+            deposit,
+        };
+
+        let buyer_key = DataKey::Member(circle_id.clone(), buyer.clone());
+        env.storage().persistent().remove(&seller_key);
+        env.storage().persistent().set(&buyer_key, &member_state);
+        env.storage()
+            .persistent()
+            .extend_ttl(&buyer_key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        state.members.set(listing.position - 1, buyer.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id.clone()), &state);
+
+        env.storage().persistent().remove(&listing_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SwapProposal(circle_id.clone(), seller.clone()));
+        env.storage().persistent().remove(&DataKey::Bid(
+            circle_id.clone(),
+            state.current_round,
+            seller.clone(),
+        ));
+        Self::clear_need_vote(&env, &circle_id, &state, &seller);
+
+        env.events().publish(
+            (Symbol::new(&env, "position_sold"),),
+            (circle_id, seller, buyer, listing.position, listing.price),
+        );
+
+        Ok(listing.position)
+    }
+
+    /// Get a member's listing.
+    pub fn get_listing(
+        env: Env,
+        circle_id: BytesN<32>,
+        seller: Address,
+    ) -> Option<PositionListing> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Listing(circle_id, seller))
+    }
+
+    // ============ Need Voting ============
+
+    /// Ask for the current round's payout in a `NeedVote` circle, during the voting window.
+    pub fn request_payout(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+        reason: String,
+    ) -> Result<NeedRequest, CircleError> {
+        member.require_auth();
+
+        let state = Self::load_voting_circle(&env, &circle_id)?;
+        Self::check_bidder(&env, &circle_id, &member)?;
+
+        // Resubmitting updates the reason and keeps the votes received
+        let key = DataKey::NeedRequest(circle_id.clone(), state.current_round, member.clone());
+        let votes = env
+            .storage()
+            .persistent()
+            .get::<_, NeedRequest>(&key)
+            .map(|request| request.votes)
+            .unwrap_or(0);
+
+        let request = NeedRequest {
+            member: member.clone(),
+            round: state.current_round,
+            reason,
+            votes,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &request);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events().publish(
+            (Symbol::new(&env, "need_request"),),
+            (circle_id, member, state.current_round),
+        );
+
+        Ok(request)
+    }
+
+    /// Vote for another member's need request. One vote per member per round.
+    pub fn vote_need(
+        env: Env,
+        circle_id: BytesN<32>,
+        voter: Address,
+        candidate: Address,
+    ) -> Result<u32, CircleError> {
+        voter.require_auth();
+
+        let state = Self::load_voting_circle(&env, &circle_id)?;
+        Self::load_member(&env, &circle_id, &voter)?;
+        if voter == candidate {
+            return Err(CircleError::InvalidVote);
+        }
+
+        let vote_key = DataKey::NeedVote(circle_id.clone(), state.current_round, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(CircleError::AlreadyVoted);
+        }
+
+        let request_key =
+            DataKey::NeedRequest(circle_id.clone(), state.current_round, candidate.clone());
+        let mut request: NeedRequest = env
+            .storage()
+            .persistent()
+            .get(&request_key)
+            .ok_or(CircleError::RequestNotFound)?;
+
+        request.votes += 1;
+        env.storage().persistent().set(&request_key, &request);
+        env.storage().persistent().set(&vote_key, &candidate);
+        env.storage()
+            .persistent()
+            .extend_ttl(&vote_key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events().publish(
+            (Symbol::new(&env, "need_vote"),),
+            (circle_id, voter, candidate, request.votes),
+        );
+
+        Ok(request.votes)
+    }
+
+    /// Get a member's need request for a round.
+    pub fn get_need_request(
+        env: Env,
+        circle_id: BytesN<32>,
+        round: u32,
+        member: Address,
+    ) -> Option<NeedRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::NeedRequest(circle_id, round, member))
+    }
+
+    // ============ Query Functions ============
+
+    /// Get circle state.
+    pub fn get_circle(env: Env, circle_id: BytesN<32>) -> Option<CircleState> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id))
+    }
+
+    /// Get circle by invite code.
+    pub fn get_circle_by_invite(env: Env, invite_code: BytesN<16>) -> Option<CircleState> {
+        let circle_id: Option<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::InviteCode(invite_code));
+
+        circle_id.and_then(|id| Self::get_circle(env.clone(), id))
+    }
+
+    /// Get member state.
+    pub fn get_member(
+        env: Env,
+        circle_id: BytesN<32>,
+        member: Address,
+    ) -> Option<MemberState> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Member(circle_id, member))
+    }
+
+    /// Check if address is a member of circle.
+    pub fn is_member(env: Env, circle_id: BytesN<32>, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Member(circle_id, address))
+    }
+
+    /// Get contribution status for current round.
+    pub fn get_contribution_status(
+        env: Env,
+        circle_id: BytesN<32>,
+    ) -> (u32, u32) {
+        let state: Option<CircleState> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()));
 
         match state {
             Some(s) => {
@@ -677,19 +1586,12 @@ impl HaloCircle {
             return Err(CircleError::InvalidConfig);
         }
 
-        state.status = CircleStatus::Active;
-        state.current_round = 1;
-        state.started_at = env.ledger().timestamp();
+        Self::activate_circle(&env, &circle_id, &mut state)?;
 
         env.storage()
             .persistent()
             .set(&DataKey::Circle(circle_id.clone()), &state);
 
-        env.events().publish(
-            (Symbol::new(&env, "circle_started"),),
-            (circle_id, state.members.len()),
-        );
-
         Ok(())
     }
 
@@ -712,6 +1614,7 @@ impl HaloCircle {
         }
 
         state.status = CircleStatus::Cancelled;
+        Self::release_deposits(&env, &circle_id, &state)?;
 
         env.storage()
             .persistent()
@@ -723,12 +1626,57 @@ impl HaloCircle {
         Ok(())
     }
 
-    // ============ Internal Functions ============
+    /// Resolve a circle still forming after its deadline. Callable by anyone. With at
+    /// least `min_members` it starts with the smaller group; otherwise it is cancelled
+    /// and deposits are refunded. Returns the resulting status.
+    pub fn expire_circle(env: Env, circle_id: BytesN<32>) -> Result<CircleStatus, CircleError> {
+        let mut state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
 
-    fn validate_config(config: &CircleConfig) -> Result<(), CircleError> {
-        // Members: 3-10
-        if config.total_members < 3 || config.total_members > 10 {
-            return Err(CircleError::InvalidConfig);
+        if state.status != CircleStatus::Forming {
+            return Err(CircleError::CircleNotForming);
+        }
+        if state.config.forming_deadline == 0
+            || env.ledger().timestamp() <= state.config.forming_deadline
+        {
+            return Err(CircleError::DeadlineNotReached);
+        }
+
+        if state.members.len() >= state.config.min_members {
+            Self::activate_circle(&env, &circle_id, &mut state)?;
+        } else {
+            state.status = CircleStatus::Cancelled;
+            Self::release_deposits(&env, &circle_id, &state)?;
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id.clone()), &state);
+
+        env.events().publish(
+            (Symbol::new(&env, "circle_expired"),),
+            (circle_id, state.members.len(), state.status),
+        );
+
+        Ok(state.status)
+    }
+
+    // ============ Internal Functions ============
+
+    fn validate_config(config: &CircleConfig) -> Result<(), CircleError> {
+        // Members: 3-10
+        if config.total_members < 3 || config.total_members > 10 {
+            return Err(CircleError::InvalidConfig);
+        }
+
+        // Minimum to start at the forming deadline: 3 up to the full group
+        if config.forming_deadline != 0
+            && (config.min_members < 3 || config.min_members > config.total_members)
+        {
+            return Err(CircleError::InvalidConfig);
         }
 
         // Contribution: positive
@@ -746,6 +1694,51 @@ impl HaloCircle {
             return Err(CircleError::InvalidConfig);
         }
 
+        // Need voting picks the recipient itself and needs a voting window
+        if config.payout_order == PayoutOrder::NeedVote
+            && (config.payout_mode != PayoutMode::Rotation
+                || config.bid_window == 0
+                || config.bid_window >= config.period_length)
+        {
+            return Err(CircleError::InvalidConfig);
+        }
+
+        // Auctions: the bidding (and reveal) windows must fit inside a period
+        match config.payout_mode {
+            PayoutMode::Rotation => {}
+            PayoutMode::OpenAuction => {
+                if config.bid_window == 0 || config.bid_window >= config.period_length {
+                    return Err(CircleError::InvalidConfig);
+                }
+            }
+            PayoutMode::SealedAuction => {
+                if config.bid_window == 0 || config.bid_window * 2 >= config.period_length {
+                    return Err(CircleError::InvalidConfig);
+                }
+            }
+        }
+
+        // Deposit: positive, at most one contribution per member of the smallest
+        // group the circle can start with
+        let smallest = if config.forming_deadline != 0 {
+            config.min_members
+        } else {
+            config.total_members
+        };
+        match config.deposit {
+            DepositRequirement::None => {}
+            DepositRequirement::Fixed(amount) => {
+                if amount <= 0 {
+                    return Err(CircleError::InvalidConfig);
+                }
+            }
+            DepositRequirement::Multiple(multiple) => {
+                if multiple == 0 || multiple > smallest {
+                    return Err(CircleError::InvalidConfig);
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -851,7 +1844,6 @@ impl HaloCircle {
         env: &Env,
         circle_id: &BytesN<32>,
         member: &Address,
-        unique_id: &BytesN<32>,
     ) -> Result<u32, CircleError> {
         let mut state: CircleState = env
             .storage()
@@ -864,28 +1856,37 @@ impl HaloCircle {
             return Err(CircleError::CircleNotForming);
         }
 
-        // Check not already member
-        if state.members.contains(member) {
-            return Err(CircleError::AlreadyMember);
+        // Joins close once the circle can be expired
+        if state.config.forming_deadline != 0
+            && env.ledger().timestamp() > state.config.forming_deadline
+        {
+            return Err(CircleError::FormingDeadlinePassed);
         }
 
+        let unique_id = Self::check_eligible(env, &state, member)?;
+
         // Check not full
         if state.members.len() >= state.config.total_members {
             return Err(CircleError::CircleFull);
         }
 
+        // Escrow the security deposit
+        let deposit = Self::escrow_deposit(env, circle_id, &state.config, member, &unique_id);
+
         // Add member
         state.members.push_back(member.clone());
         let position = state.members.len() as u32;
 
         // Create member state
         let member_state = MemberState {
-            unique_id: unique_id.clone(),
+            unique_id,
             payout_position: position,
             joined_at: env.ledger().timestamp(),
             total_contributed: 0,
             has_received_payout: false,
             rounds_contributed: Vec::new(env),
+            rounds_missed: Vec::new(env),
+            deposit,
         };
 
         env.storage().persistent().set(
@@ -895,14 +1896,7 @@ impl HaloCircle {
 
         // Auto-start if full
         if state.members.len() == state.config.total_members {
-            state.status = CircleStatus::Active;
-            state.current_round = 1;
-            state.started_at = env.ledger().timestamp();
-
-            env.events().publish(
-                (Symbol::new(&env, "circle_started"),),
-                (circle_id.clone(), state.config.total_members),
-            );
+            Self::activate_circle(env, circle_id, &mut state)?;
         }
 
         env.storage()
@@ -916,169 +1910,2077 @@ impl HaloCircle {
             100 * 24 * 60 * 60,
         );
 
-        Ok(position)
-    }
+        Ok(position)
+    }
+
+    /// Check that a wallet can take a place in the circle, by joining or by buying a
+    /// position, and return its unique ID.
+    fn check_eligible(
+        env: &Env,
+        state: &CircleState,
+        member: &Address,
+    ) -> Result<BytesN<32>, CircleError> {
+        if state.members.contains(member) {
+            return Err(CircleError::AlreadyMember);
+        }
+
+        // Verify member has bound identity
+        Self::verify_identity(env, member)?;
+        Self::get_unique_id(env, member)
+    }
+
+    /// Move a forming circle to `Active` and fix its payout order.
+    fn activate_circle(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+    ) -> Result<(), CircleError> {
+        // A circle started short runs one round per member, which also sizes the pot
+        state.status = CircleStatus::Active;
+        state.current_round = 1;
+        state.started_at = env.ledger().timestamp();
+        state.config.total_members = state.members.len();
+
+        // A deposit multiple must still fit the smaller group
+        if let DepositRequirement::Multiple(multiple) = state.config.deposit {
+            if multiple > state.config.total_members {
+                return Err(CircleError::InvalidConfig);
+            }
+        }
+
+        match state.config.payout_order {
+            PayoutOrder::JoinOrder | PayoutOrder::NeedVote => {}
+            PayoutOrder::Random => env.prng().shuffle(&mut state.members),
+            PayoutOrder::CreditScore => Self::sort_by_credit_score(env, circle_id, state)?,
+        }
+        for (index, member) in state.members.iter().enumerate() {
+            Self::set_payout_position(env, circle_id, &member, index as u32 + 1)?;
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "circle_started"),),
+            (circle_id.clone(), state.members.len()),
+        );
+        env.events().publish(
+            (Symbol::new(env, "payout_order"),),
+            (circle_id.clone(), state.members.clone()),
+        );
+
+        Ok(())
+    }
+
+    fn round_start(state: &CircleState) -> u64 {
+        state.started_at
+            + state.pause_offset
+            + ((state.current_round as u64 - 1) * state.config.period_length)
+    }
+
+    /// When the current round's recipient can be chosen (bids revealed, if sealed).
+    fn bidding_closes_at(state: &CircleState) -> u64 {
+        if state.current_round >= state.config.total_members {
+            return 0;
+        }
+        let windows = match state.config.payout_mode {
+            PayoutMode::Rotation if state.config.payout_order == PayoutOrder::NeedVote => 1,
+            PayoutMode::Rotation => return 0,
+            PayoutMode::OpenAuction => 1,
+            PayoutMode::SealedAuction => 2,
+        };
+        Self::round_start(state) + state.config.bid_window * windows
+    }
+
+    /// Stable sort of the members by credit score, highest first.
+    fn sort_by_credit_score(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+    ) -> Result<(), CircleError> {
+        let mut scores: Vec<u32> = Vec::new(env);
+        for member in state.members.iter() {
+            let member_state = Self::load_member(env, circle_id, &member)?;
+            scores.push_back(Self::credit_score(env, &member_state.unique_id));
+        }
+
+        let mut sorted: Vec<Address> = Vec::new(env);
+        let mut sorted_scores: Vec<u32> = Vec::new(env);
+        for (member, score) in state.members.iter().zip(scores.iter()) {
+            let mut index = sorted_scores.len();
+            while index > 0 && sorted_scores.get_unchecked(index - 1) < score {
+                index -= 1;
+            }
+            sorted.insert(index, member);
+            sorted_scores.insert(index, score);
+        }
+
+        state.members = sorted;
+        Ok(())
+    }
+
+    fn load_voting_circle(env: &Env, circle_id: &BytesN<32>) -> Result<CircleState, CircleError> {
+        let state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        if state.config.payout_order != PayoutOrder::NeedVote {
+            return Err(CircleError::WrongPayoutMode);
+        }
+        if state.status != CircleStatus::Active {
+            return Err(CircleError::CircleNotActive);
+        }
+        if env.ledger().timestamp() >= Self::round_start(&state) + state.config.bid_window {
+            return Err(CircleError::BiddingClosed);
+        }
+
+        Ok(state)
+    }
+
+    /// Most-voted need request among members still waiting for a payout; ties go to
+    /// the earlier payout position.
+    fn leading_need(env: &Env, circle_id: &BytesN<32>, state: &CircleState) -> Option<NeedRequest> {
+        let mut leader: Option<NeedRequest> = None;
+        for index in (state.current_round - 1)..state.members.len() {
+            let member = state.members.get(index)?;
+            let request: Option<NeedRequest> = env.storage().persistent().get(
+                &DataKey::NeedRequest(circle_id.clone(), state.current_round, member),
+            );
+            if let Some(request) = request {
+                let leads = match &leader {
+                    Some(current) => request.votes > current.votes,
+                    None => true,
+                };
+                if leads {
+                    leader = Some(request);
+                }
+            }
+        }
+        leader
+    }
+
+    /// Withdraw the member's need vote and request for a round, along with any votes
+    /// cast for that request, so a departing member leaves no say in the round.
+    fn clear_need_vote(env: &Env, circle_id: &BytesN<32>, state: &CircleState, member: &Address) {
+        let round = state.current_round;
+        let vote_key = DataKey::NeedVote(circle_id.clone(), round, member.clone());
+        if let Some(candidate) = env.storage().persistent().get::<_, Address>(&vote_key) {
+            let request_key = DataKey::NeedRequest(circle_id.clone(), round, candidate);
+            let request: Option<NeedRequest> = env.storage().persistent().get(&request_key);
+            if let Some(mut request) = request {
+                request.votes = request.votes.saturating_sub(1);
+                env.storage().persistent().set(&request_key, &request);
+            }
+            env.storage().persistent().remove(&vote_key);
+        }
+
+        let request_key = DataKey::NeedRequest(circle_id.clone(), round, member.clone());
+        if env.storage().persistent().has(&request_key) {
+            env.storage().persistent().remove(&request_key);
+            for voter in state.members.iter() {
+                let key = DataKey::NeedVote(circle_id.clone(), round, voter);
+                if env.storage().persistent().get::<_, Address>(&key).as_ref() == Some(member) {
+                    env.storage().persistent().remove(&key);
+                }
+            }
+        }
+    }
+
+    fn load_bidding_circle(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        mode: PayoutMode,
+    ) -> Result<CircleState, CircleError> {
+        let state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        if state.config.payout_mode != mode {
+            return Err(CircleError::WrongPayoutMode);
+        }
+        if state.status != CircleStatus::Active {
+            return Err(CircleError::CircleNotActive);
+        }
+
+        Ok(state)
+    }
+
+    fn check_bidder(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        member: &Address,
+    ) -> Result<(), CircleError> {
+        let member_state = Self::load_member(env, circle_id, member)?;
+        if member_state.has_received_payout {
+            return Err(CircleError::PayoutAlreadyProcessed);
+        }
+        Ok(())
+    }
+
+    fn check_discount(state: &CircleState, discount: i128) -> Result<(), CircleError> {
+        let pot = state.config.contribution_amount * state.config.total_members as i128;
+        if discount < 0 || discount >= pot {
+            return Err(CircleError::InvalidBid);
+        }
+        Ok(())
+    }
+
+    fn store_bid(env: &Env, circle_id: &BytesN<32>, bid: &Bid) {
+        let key = DataKey::Bid(circle_id.clone(), bid.round, bid.bidder.clone());
+        env.storage().persistent().set(&key, bid);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+
+    /// Highest revealed bid among members still waiting for a payout; ties go to the
+    /// earlier payout position.
+    fn leading_bid(env: &Env, circle_id: &BytesN<32>, state: &CircleState) -> Option<Bid> {
+        let mut leader: Option<Bid> = None;
+        for index in (state.current_round - 1)..state.members.len() {
+            let member = state.members.get(index)?;
+            let bid: Option<Bid> = env.storage().persistent().get(&DataKey::Bid(
+                circle_id.clone(),
+                state.current_round,
+                member,
+            ));
+            if let Some(bid) = bid {
+                let leads = match &leader {
+                    Some(current) => bid.discount > current.discount,
+                    None => true,
+                };
+                if bid.revealed && leads {
+                    leader = Some(bid);
+                }
+            }
+        }
+        leader
+    }
+
+    /// Move the round's auction or vote winner into the current payout position and
+    /// return the discount they bid. Otherwise the member already in that position is paid.
+    fn resolve_recipient(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+    ) -> Result<i128, CircleError> {
+        // The last round has a single member left to pay
+        if state.current_round >= state.config.total_members {
+            return Ok(0);
+        }
+        if env.ledger().timestamp() < Self::bidding_closes_at(state) {
+            return Err(CircleError::BiddingOpen);
+        }
+
+        let round = state.current_round;
+        if state.config.payout_order == PayoutOrder::NeedVote {
+            if let Some(request) = Self::leading_need(env, circle_id, state) {
+                Self::move_to_current_position(env, circle_id, state, &request.member)?;
+
+                env.events().publish(
+                    (Symbol::new(env, "need_selected"),),
+                    (circle_id.clone(), round, request.member, request.votes),
+                );
+            }
+            return Ok(0);
+        }
+
+        if state.config.payout_mode == PayoutMode::Rotation {
+            return Ok(0);
+        }
+
+        let winner = match Self::leading_bid(env, circle_id, state) {
+            Some(bid) => bid,
+            None => return Ok(0),
+        };
+        Self::move_to_current_position(env, circle_id, state, &winner.bidder)?;
+
+        env.events().publish(
+            (Symbol::new(env, "auction_won"),),
+            (circle_id.clone(), round, winner.bidder, winner.discount),
+        );
+
+        Ok(winner.discount)
+    }
+
+    /// Swap `member` into the current round's payout position.
+    fn move_to_current_position(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+        member: &Address,
+    ) -> Result<(), CircleError> {
+        let member_index = state
+            .members
+            .first_index_of(member)
+            .ok_or(CircleError::NotMember)?;
+        Self::swap_positions(env, circle_id, state, state.current_round - 1, member_index)
+    }
+
+    /// Exchange the members at two indexes of `state.members` and renumber both.
+    fn swap_positions(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+        a: u32,
+        b: u32,
+    ) -> Result<(), CircleError> {
+        if a == b {
+            return Ok(());
+        }
+
+        let member_a = state.members.get(a).ok_or(CircleError::InvalidRound)?;
+        let member_b = state.members.get(b).ok_or(CircleError::InvalidRound)?;
+        state.members.set(a, member_b.clone());
+        state.members.set(b, member_a.clone());
+        Self::set_payout_position(env, circle_id, &member_b, a + 1)?;
+        Self::set_payout_position(env, circle_id, &member_a, b + 1)?;
+
+        Ok(())
+    }
+
+    /// Seller's position if it can be sold: an unpaid future round held by a member
+    /// other than the creator with no missed payments.
+    fn check_sellable(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &CircleState,
+        seller: &Address,
+    ) -> Result<u32, CircleError> {
+        if state.status != CircleStatus::Active {
+            return Err(CircleError::CircleNotActive);
+        }
+
+        let member_state = Self::load_member(env, circle_id, seller)?;
+        if *seller == state.creator
+            || member_state.has_received_payout
+            || !member_state.rounds_missed.is_empty()
+            || member_state.payout_position <= state.current_round
+        {
+            return Err(CircleError::PositionNotTransferable);
+        }
+
+        Ok(member_state.payout_position)
+    }
+
+    fn load_swap_circle(env: &Env, circle_id: &BytesN<32>) -> Result<CircleState, CircleError> {
+        let state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        // Auctions and need voting assign positions round by round
+        if state.config.payout_mode != PayoutMode::Rotation
+            || state.config.payout_order == PayoutOrder::NeedVote
+        {
+            return Err(CircleError::WrongPayoutMode);
+        }
+        if state.status != CircleStatus::Active {
+            return Err(CircleError::CircleNotActive);
+        }
+
+        Ok(state)
+    }
+
+    /// Member holding a swappable position: a future round that has not been paid.
+    fn check_swap_position(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &CircleState,
+        position: u32,
+    ) -> Result<Address, CircleError> {
+        if position <= state.current_round || position > state.members.len() {
+            return Err(CircleError::InvalidSwap);
+        }
+
+        let member = state
+            .members
+            .get(position - 1)
+            .ok_or(CircleError::InvalidSwap)?;
+        if Self::load_member(env, circle_id, &member)?.has_received_payout {
+            return Err(CircleError::InvalidSwap);
+        }
+
+        Ok(member)
+    }
+
+    fn set_payout_position(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        member: &Address,
+        position: u32,
+    ) -> Result<(), CircleError> {
+        let mut member_state = Self::load_member(env, circle_id, member)?;
+        member_state.payout_position = position;
+        env.storage().persistent().set(
+            &DataKey::Member(circle_id.clone(), member.clone()),
+            &member_state,
+        );
+        Ok(())
+    }
+
+    fn load_member(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        member: &Address,
+    ) -> Result<MemberState, CircleError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Member(circle_id.clone(), member.clone()))
+            .ok_or(CircleError::NotMember)
+    }
+
+    /// Deposit owed by a joining member after their credit tier discount.
+    fn required_deposit(env: &Env, config: &CircleConfig, unique_id: &BytesN<32>) -> i128 {
+        let base = match config.deposit {
+            DepositRequirement::None => return 0,
+            DepositRequirement::Fixed(amount) => amount,
+            DepositRequirement::Multiple(multiple) => config.contribution_amount * multiple as i128,
+        };
+
+        let discount = DEPOSIT_TIER_DISCOUNTS[Self::credit_tier_index(env, unique_id)];
+        base - base * discount as i128 / 100
+    }
+
+    /// Index of the member's credit tier in `DEPOSIT_TIER_DISCOUNTS` (0 when unscored).
+    fn credit_tier_index(env: &Env, unique_id: &BytesN<32>) -> usize {
+        #[cfg(feature = "cross-contract")]
+        {
+            if let Some(credit_contract) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::CreditContract)
+            {
+                let client = credit_client::Client::new(env, &credit_contract);
+                if let Ok(Ok(Some(tier))) = client.try_get_tier(unique_id) {
+                    return match tier {
+                        credit_client::ScoreTier::Building => 0,
+                        credit_client::ScoreTier::Fair => 1,
+                        credit_client::ScoreTier::Good => 2,
+                        credit_client::ScoreTier::Excellent => 3,
+                    };
+                }
+            }
+        }
+
+        // When cross-contract feature is disabled, no discount applies
+        #[cfg(not(feature = "cross-contract"))]
+        let _ = (env, unique_id);
+
+        0
+    }
+
+    /// Member's credit score from the Credit contract (0 when unscored).
+    fn credit_score(env: &Env, unique_id: &BytesN<32>) -> u32 {
+        if let Some(credit_contract) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::CreditContract)
+        {
+            let client = CreditScoreClient::new(env, &credit_contract);
+            if let Ok(Ok(Some(score))) = client.try_get_score(unique_id) {
+                return score;
+            }
+        }
+
+        0
+    }
+
+    /// Take the member's security deposit into escrow and return the amount.
+    fn escrow_deposit(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        config: &CircleConfig,
+        member: &Address,
+        unique_id: &BytesN<32>,
+    ) -> i128 {
+        let deposit = Self::required_deposit(env, config, unique_id);
+        if deposit > 0 {
+            let token = token::Client::new(env, &config.contribution_token);
+            token.transfer(member, &env.current_contract_address(), &deposit);
+
+            env.events().publish(
+                (Symbol::new(env, "deposit_escrowed"),),
+                (circle_id.clone(), member.clone(), deposit),
+            );
+        }
+        deposit
+    }
+
+    /// Return every member's remaining deposit.
+    fn release_deposits(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &CircleState,
+    ) -> Result<(), CircleError> {
+        let token = token::Client::new(env, &state.config.contribution_token);
+        for member in state.members.iter() {
+            let mut member_state = Self::load_member(env, circle_id, &member)?;
+            if member_state.deposit == 0 {
+                continue;
+            }
+
+            let amount = member_state.deposit;
+            token.transfer(&env.current_contract_address(), &member, &amount);
+            member_state.deposit = 0;
+            env.storage().persistent().set(
+                &DataKey::Member(circle_id.clone(), member.clone()),
+                &member_state,
+            );
+
+            env.events().publish(
+                (Symbol::new(env, "deposit_released"),),
+                (circle_id.clone(), member, amount),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Share what is left in the reserve equally among the members; any remainder
+    /// goes to the first member in payout order.
+    fn release_reserve(env: &Env, circle_id: &BytesN<32>, state: &mut CircleState) {
+        if state.reserve <= 0 {
+            return;
+        }
+
+        let token = token::Client::new(env, &state.config.contribution_token);
+        let share = state.reserve / state.members.len() as i128;
+        let mut remainder = state.reserve - share * state.members.len() as i128;
+        for member in state.members.iter() {
+            let amount = share + remainder;
+            remainder = 0;
+            if amount > 0 {
+                token.transfer(&env.current_contract_address(), &member, &amount);
+            }
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "reserve_released"),),
+            (circle_id.clone(), state.reserve, share),
+        );
+
+        state.total_paid_out += state.reserve;
+        state.reserve = 0;
+    }
+
+    /// Drop a member from a forming circle: refund their deposit, delete their member
+    /// entry and close the gap in payout positions.
+    fn remove_forming_member(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+        member: &Address,
+    ) -> Result<(), CircleError> {
+        if state.status != CircleStatus::Forming {
+            return Err(CircleError::CircleNotForming);
+        }
+
+        let index = state
+            .members
+            .first_index_of(member)
+            .ok_or(CircleError::NotMember)?;
+        let member_state = Self::load_member(env, circle_id, member)?;
+
+        let deposit = member_state.deposit;
+        if deposit > 0 {
+            let token = token::Client::new(env, &state.config.contribution_token);
+            token.transfer(&env.current_contract_address(), member, &deposit);
+
+            env.events().publish(
+                (Symbol::new(env, "deposit_released"),),
+                (circle_id.clone(), member.clone(), deposit),
+            );
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Member(circle_id.clone(), member.clone()));
+        Self::clear_need_vote(env, circle_id, state, member);
+
+        state.members.remove(index);
+        for position in index..state.members.len() {
+            let moved = state.members.get_unchecked(position);
+            Self::set_payout_position(env, circle_id, &moved, position + 1)?;
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id.clone()), state);
+
+        Ok(())
+    }
+
+    fn count_contributions(env: &Env, circle_id: &BytesN<32>, state: &CircleState) -> u32 {
+        let mut count = 0u32;
+        for member in state.members.iter() {
+            if let Some(member_state) = env
+                .storage()
+                .persistent()
+                .get::<_, MemberState>(&DataKey::Member(circle_id.clone(), member))
+            {
+                if member_state.rounds_contributed.contains(&state.current_round) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Pay the current round's recipient the full pot and advance the circle.
+    fn execute_payout(
+        env: &Env,
+        circle_id: BytesN<32>,
+        state: CircleState,
+    ) -> Result<PayoutRecord, CircleError> {
+        if state.status != CircleStatus::Active && state.status != CircleStatus::Paused {
+            return Err(CircleError::CircleNotActive);
+        }
+
+        // Count contributions for this round
+        let contributions = Self::count_contributions(env, &circle_id, &state);
+
+        // All members must have contributed
+        if contributions < state.config.total_members {
+            return Err(CircleError::ContributionsIncomplete);
+        }
+
+        // Calculate payout amount
+        let payout_amount = state.config.contribution_amount * state.config.total_members as i128;
+
+        Self::pay_recipient(env, circle_id, state, payout_amount)
+    }
+
+    /// Transfer `payout_amount` to the current round's recipient and advance the circle.
+    fn pay_recipient(
+        env: &Env,
+        circle_id: BytesN<32>,
+        mut state: CircleState,
+        payout_amount: i128,
+    ) -> Result<PayoutRecord, CircleError> {
+        // Get recipient (member at current round position, after any auction)
+        let discount = Self::resolve_recipient(env, &circle_id, &mut state)?.min(payout_amount);
+        let recipient = state
+            .members
+            .get(state.current_round - 1)
+            .ok_or(CircleError::InvalidRound)?;
+
+        // Transfer payout, less any auction discount
+        let amount = payout_amount - discount;
+        let token = token::Client::new(env, &state.config.contribution_token);
+        if amount > 0 {
+            token.transfer(&env.current_contract_address(), &recipient, &amount);
+        }
+
+        // Split the discount among the other members; the remainder joins the reserve
+        let mut dividends: i128 = 0;
+        if discount > 0 {
+            let share = discount / (state.members.len() - 1) as i128;
+            for member in state.members.iter() {
+                if member == recipient || share == 0 {
+                    continue;
+                }
+                token.transfer(&env.current_contract_address(), &member, &share);
+                dividends += share;
+            }
+            state.reserve += discount - dividends;
+
+            env.events().publish(
+                (Symbol::new(env, "dividend"),),
+                (circle_id.clone(), state.current_round, share),
+            );
+        }
+
+        // Update member state
+        let mut recipient_state: MemberState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Member(circle_id.clone(), recipient.clone()))
+            .unwrap();
+        recipient_state.has_received_payout = true;
+        env.storage().persistent().set(
+            &DataKey::Member(circle_id.clone(), recipient.clone()),
+            &recipient_state,
+        );
+
+        // Update circle state
+        let current_time = env.ledger().timestamp();
+        let resumed = state.status == CircleStatus::Paused;
+        state.total_paid_out += amount + dividends;
+        state.current_round += 1;
+        state.status = CircleStatus::Active;
+
+        // A paused circle resumes with the next round starting now
+        let next_start = Self::round_start(&state);
+        if resumed && current_time > next_start {
+            state.pause_offset += current_time - next_start;
+        }
+
+        // Check if circle is complete
+        if state.current_round > state.config.total_members {
+            state.status = CircleStatus::Completed;
+            Self::finalize_circle(env, &circle_id, &mut state)?;
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id.clone()), &state);
+
+        let record = PayoutRecord {
+            recipient: recipient.clone(),
+            round: state.current_round - 1,
+            amount,
+            discount,
+            timestamp: current_time,
+        };
+
+        env.events().publish(
+            (Symbol::new(env, "payout"), symbol_short!("out")),
+            (circle_id, recipient, state.current_round - 1, amount),
+        );
+
+        Ok(record)
+    }
+
+    fn try_process_payout(env: &Env, circle_id: BytesN<32>) -> Result<(), CircleError> {
+        let state: CircleState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id.clone()))
+            .ok_or(CircleError::CircleNotFound)?;
+
+        let contributions = Self::count_contributions(env, &circle_id, &state);
+
+        let bidding_open = env.ledger().timestamp() < Self::bidding_closes_at(&state);
+
+        let all_contributed = contributions == state.config.total_members;
+        if state.config.auto_payout && !bidding_open && all_contributed {
+            Self::execute_payout(env, circle_id, state)?;
+        }
+
+        Ok(())
+    }
+
+    fn record_payment_to_credit(
+        env: &Env,
+        unique_id: &BytesN<32>,
+        circle_id: &BytesN<32>,
+        round: u32,
+        token: &Address,
+        amount: i128,
+        on_time: bool,
+    ) {
+        #[cfg(feature = "cross-contract")]
+        {
+            // Get the Credit contract address
+            if let Some(credit_contract) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::CreditContract)
+            {
+                // Create client for Credit contract
+                let client = credit_client::Client::new(env, &credit_contract);
+
+                // Record payment (Circle contract must be authorized in Credit contract)
+                // Ignore errors - payment recording is not critical for circle operation
+                let _ = client.record_payment(
+                    &env.current_contract_address(),
+                    unique_id,
+                    circle_id,
+                    &round,
+                    token,
+                    &amount,
+                    &on_time,
+                );
+            }
+        }
+
+        // When cross-contract feature is disabled, do nothing
+        #[cfg(not(feature = "cross-contract"))]
+        let _ = (env, unique_id, circle_id, round, token, amount, on_time);
+    }
+
+    fn record_missed_payment_to_credit(
+        env: &Env,
+        unique_id: &BytesN<32>,
+        circle_id: &BytesN<32>,
+        round: u32,
+    ) {
+        #[cfg(feature = "cross-contract")]
+        {
+            if let Some(credit_contract) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::CreditContract)
+            {
+                let client = credit_client::Client::new(env, &credit_contract);
+
+                // Ignore errors - credit reporting must not block settlement
+                let _ = client.record_missed_payment(
+                    &env.current_contract_address(),
+                    unique_id,
+                    circle_id,
+                    &round,
+                );
+            }
+        }
+
+        #[cfg(not(feature = "cross-contract"))]
+        let _ = (env, unique_id, circle_id, round);
+    }
+
+    fn finalize_circle(
+        env: &Env,
+        circle_id: &BytesN<32>,
+        state: &mut CircleState,
+    ) -> Result<(), CircleError> {
+        #[cfg(feature = "cross-contract")]
+        {
+            // Get the Credit contract address
+            if let Some(credit_contract) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::CreditContract)
+            {
+                // Create client for Credit contract
+                let client = credit_client::Client::new(env, &credit_contract);
+
+                // Record circle completion for all members
+                for member in state.members.iter() {
+                    if let Some(member_state) = env
+                        .storage()
+                        .persistent()
+                        .get::<_, MemberState>(&DataKey::Member(circle_id.clone(), member))
+                    {
+                        // Members with missed rounds completed unsuccessfully (ignore errors)
+                        let _ = client.record_circle_completion(
+                            &env.current_contract_address(),
+                            &member_state.unique_id,
+                            circle_id,
+                            &member_state.rounds_missed.is_empty(),
+                        );
+                    }
+                }
+            }
+        }
+
+        // When cross-contract feature is disabled, just emit event
+        #[cfg(not(feature = "cross-contract"))]
+        let _ = state.members.iter();
+
+        Self::release_deposits(env, circle_id, state)?;
+        Self::release_reserve(env, circle_id, state);
+
+        env.events().publish(
+            (Symbol::new(&env, "circle_completed"),),
+            (circle_id.clone(), state.total_contributed, state.total_paid_out),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use halo_credit::{HaloCredit, HaloCreditClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{token, Env};
+
+    fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+        let contract_id = env.register_stellar_asset_contract_v2(admin.clone());
+        token::Client::new(env, &contract_id.address())
+    }
+
+    fn create_config(env: &Env, token: &Address) -> CircleConfig {
+        CircleConfig {
+            name: Symbol::new(env, "TestCircle"),
+            contribution_amount: 100_000_000, // 100 USDC (6 decimals)
+            contribution_token: token.clone(),
+            total_members: 3,
+            period_length: 86400 * 30, // 30 days
+            grace_period: 86400 * 7,   // 7 days
+            late_fee_percent: 5,
+            auto_payout: true,
+            default_policy: DefaultPolicy::PayoutCollected,
+            deposit: DepositRequirement::None,
+            payout_order: PayoutOrder::JoinOrder,
+            payout_mode: PayoutMode::Rotation,
+            bid_window: 0,
+            forming_deadline: 0,
+            min_members: 3,
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+
+        client.initialize(&admin, &identity, &credit);
+
+        assert_eq!(client.get_admin(), admin);
+        assert_eq!(client.get_circle_count(), 0);
+    }
+
+    #[test]
+    fn test_create_circle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let config = create_config(&env, &token.address);
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Forming);
+        assert_eq!(circle.members.len(), 1); // Creator is first member
+        assert_eq!(client.get_circle_count(), 1);
+    }
+
+    #[test]
+    fn test_join_circle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let config = create_config(&env, &token.address);
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+
+        // Get invite code
+        let circle = client.get_circle(&circle_id).unwrap();
+        let invite_code = circle.invite_code;
+
+        // Join with member 2
+        let position2 = client.join_circle(&invite_code, &member2);
+        assert_eq!(position2, 2);
+
+        // Join with member 3 - circle should auto-start
+        let position3 = client.join_circle(&invite_code, &member3);
+        assert_eq!(position3, 3);
+
+        // Circle should now be active
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Active);
+        assert_eq!(circle.current_round, 1);
+    }
+
+    #[test]
+    fn test_contribute() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let config = create_config(&env, &token.address);
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let circle = client.get_circle(&circle_id).unwrap();
+        let invite_code = circle.invite_code;
+
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        // Mint tokens to members
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &1_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &1_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &1_000_000_000);
+
+        // Make contributions
+        let record1 = client.contribute(&circle_id, &creator);
+        assert!(record1.on_time);
+
+        let record2 = client.contribute(&circle_id, &member2);
+        assert!(record2.on_time);
+
+        // Check contribution status
+        let (contributed, total) = client.get_contribution_status(&circle_id);
+        assert_eq!(contributed, 2);
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_full_circle_lifecycle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let config = create_config(&env, &token.address);
+
+        client.initialize(&admin, &identity, &credit);
+
+        // Create and fill circle
+        let circle_id = client.create_circle(&creator, &config);
+        let circle = client.get_circle(&circle_id).unwrap();
+        let invite_code = circle.invite_code;
+
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        // Mint tokens
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+
+        let token_client = token::Client::new(&env, &token.address);
+        let members = [creator.clone(), member2.clone(), member3.clone()];
+
+        // Each round pays out with the last contribution, no process_payout calls
+        for (i, recipient) in members.iter().enumerate() {
+            let before = token_client.balance(recipient);
+            for member in members.iter() {
+                client.contribute(&circle_id, member);
+            }
+
+            // Recipient paid in the pot and received all three contributions
+            assert_eq!(token_client.balance(recipient), before + 200_000_000);
+            assert!(client.get_member(&circle_id, recipient).unwrap().has_received_payout);
+
+            let circle = client.get_circle(&circle_id).unwrap();
+            assert_eq!(circle.current_round, i as u32 + 2);
+        }
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Completed);
+        assert_eq!(circle.total_contributed, 900_000_000);
+        assert_eq!(circle.total_paid_out, 900_000_000);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        for member in members.iter() {
+            assert_eq!(token_client.balance(member), 10_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_manual_payout_when_auto_disabled() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.auto_payout = false;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+        client.contribute(&circle_id, &member3);
+
+        // Nothing paid out until the creator triggers it
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.current_round, 1);
+        assert_eq!(circle.total_paid_out, 0);
+
+        let payout = client.process_payout(&circle_id);
+        assert_eq!(payout.round, 1);
+        assert_eq!(payout.recipient, creator);
+        assert_eq!(payout.amount, 300_000_000);
+        assert_eq!(env.auths()[0].0, creator);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.current_round, 2);
+    }
+
+    #[test]
+    fn test_settle_round_pays_collected() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.default_policy = DefaultPolicy::PayoutCollected;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+
+        // Cannot settle before the grace period ends (30 day period + 7 day grace)
+        env.ledger().set_timestamp(86400 * 37);
+        assert_eq!(
+            client.try_settle_round(&circle_id).err(),
+            Some(Ok(CircleError::GracePeriodActive))
+        );
+
+        env.ledger().set_timestamp(86400 * 37 + 1);
+        let settlement = client.settle_round(&circle_id);
+        assert_eq!(settlement.round, 1);
+        assert_eq!(settlement.missed, Vec::from_array(&env, [member3.clone()]));
+        assert_eq!(settlement.collected, 200_000_000);
+        assert_eq!(settlement.covered, 0);
+        assert_eq!(settlement.paid_out, 200_000_000);
+
+        // Creator paid 100 and received the 200 collected
+        assert_eq!(token_client.balance(&creator), 10_100_000_000);
+
+        let missed = client.get_member(&circle_id, &member3).unwrap();
+        assert_eq!(missed.rounds_missed, Vec::from_array(&env, [1u32]));
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Active);
+        assert_eq!(circle.current_round, 2);
+        assert_eq!(circle.total_paid_out, 200_000_000);
+    }
+
+    #[test]
+    fn test_settle_round_cover_shortfall() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.default_policy = DefaultPolicy::CoverShortfall;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        // Both contributions land after the due date and pay a 5% late fee into the reserve
+        env.ledger().set_timestamp(86400 * 31);
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+        assert_eq!(client.get_circle(&circle_id).unwrap().reserve, 10_000_000);
+
+        env.ledger().set_timestamp(86400 * 38);
+        let settlement = client.settle_round(&circle_id);
+        assert_eq!(settlement.collected, 200_000_000);
+        assert_eq!(settlement.covered, 10_000_000);
+        assert_eq!(settlement.paid_out, 210_000_000);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.reserve, 0);
+        assert_eq!(circle.current_round, 2);
+        assert_eq!(token_client.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_settle_round_pause() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.default_policy = DefaultPolicy::Pause;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+
+        env.ledger().set_timestamp(86400 * 38);
+        let settlement = client.settle_round(&circle_id);
+        assert_eq!(settlement.paid_out, 0);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Paused);
+        assert_eq!(circle.current_round, 1);
+
+        // The missing contribution arrives late and releases the payout
+        env.ledger().set_timestamp(86400 * 50);
+        client.contribute(&circle_id, &member3);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Active);
+        assert_eq!(circle.current_round, 2);
+        assert_eq!(circle.total_paid_out, 300_000_000);
+        assert_eq!(circle.reserve, 5_000_000);
+        assert_eq!(token_client.balance(&contract_id), 5_000_000);
+
+        // Round 2 starts at the resume, so its deadlines move back by the pause
+        assert_eq!(circle.pause_offset, 86400 * 20);
+        env.ledger().set_timestamp(86400 * 70);
+        assert_eq!(client.contribute(&circle_id, &creator).late_fee, 0);
+        assert_eq!(
+            client.try_settle_round(&circle_id).err(),
+            Some(Ok(CircleError::GracePeriodActive))
+        );
+        client.contribute(&circle_id, &member2);
+        client.contribute(&circle_id, &member3);
+
+        env.ledger().set_timestamp(86400 * 90);
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+        client.contribute(&circle_id, &member3);
+
+        // The late fee is shared out when the circle completes
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Completed);
+        assert_eq!(circle.reserve, 0);
+        assert_eq!(circle.total_paid_out, 905_000_000);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert_eq!(token_client.balance(&member3), 10_000_000_000 - 5_000_000 + 1_666_666);
+    }
+
+    #[test]
+    fn test_paused_circle_settles_without_defaulter() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.default_policy = DefaultPolicy::Pause;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+
+        env.ledger().set_timestamp(86400 * 38);
+        client.settle_round(&circle_id);
+        assert_eq!(client.get_circle(&circle_id).unwrap().status, CircleStatus::Paused);
+
+        // The defaulter gets one more grace period
+        env.ledger().set_timestamp(86400 * 44);
+        assert_eq!(
+            client.try_settle_round(&circle_id).err(),
+            Some(Ok(CircleError::GracePeriodActive))
+        );
+
+        // Then the collected contributions are paid out and the circle moves on
+        env.ledger().set_timestamp(86400 * 45);
+        let settlement = client.settle_round(&circle_id);
+        assert_eq!(settlement.round, 1);
+        assert_eq!(settlement.missed.len(), 1);
+        assert_eq!(settlement.paid_out, 200_000_000);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Active);
+        assert_eq!(circle.current_round, 2);
+        assert_eq!(token_client.balance(&contract_id), 0);
+
+        let defaulter = client.get_member(&circle_id, &member3).unwrap();
+        assert_eq!(defaulter.rounds_missed.len(), 1);
+    }
+
+    #[test]
+    fn test_deposits_escrowed_and_released() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.deposit = DepositRequirement::Multiple(1);
+
+        client.initialize(&admin, &identity, &credit);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        // Cancelling a forming circle returns the creator's deposit
+        let cancelled = client.create_circle(&creator, &config);
+        assert_eq!(token_client.balance(&creator), 9_900_000_000);
+        client.cancel_circle(&cancelled);
+        assert_eq!(token_client.balance(&creator), 10_000_000_000);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        assert_eq!(client.get_member(&circle_id, &member2).unwrap().deposit, 100_000_000);
+        assert_eq!(token_client.balance(&contract_id), 300_000_000);
+
+        let members = [creator.clone(), member2.clone(), member3.clone()];
+        for _ in 0..3 {
+            for member in members.iter() {
+                client.contribute(&circle_id, member);
+            }
+        }
+
+        // Completion releases every deposit
+        assert_eq!(client.get_circle(&circle_id).unwrap().status, CircleStatus::Completed);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        for member in members.iter() {
+            assert_eq!(client.get_member(&circle_id, member).unwrap().deposit, 0);
+            assert_eq!(token_client.balance(member), 10_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_deposit_slashed_on_default() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.deposit = DepositRequirement::Multiple(1);
+
+        client.initialize(&admin, &identity, &credit);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+
+        env.ledger().set_timestamp(86400 * 38);
+        let settlement = client.settle_round(&circle_id);
+        assert_eq!(settlement.collected, 200_000_000);
+        assert_eq!(settlement.slashed, 100_000_000);
+        assert_eq!(settlement.paid_out, 300_000_000);
+
+        assert_eq!(client.get_member(&circle_id, &member3).unwrap().deposit, 0);
+        assert_eq!(client.get_member(&circle_id, &member2).unwrap().deposit, 100_000_000);
+
+        // Only the two untouched deposits remain in escrow
+        assert_eq!(token_client.balance(&contract_id), 200_000_000);
+    }
+
+    #[test]
+    fn test_open_auction_payout() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.payout_mode = PayoutMode::OpenAuction;
+        config.bid_window = 86400;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        client.place_bid(&circle_id, &member3, &30_000_000);
+        assert_eq!(
+            client.try_place_bid(&circle_id, &creator, &20_000_000).err(),
+            Some(Ok(CircleError::InvalidBid))
+        );
+        client.place_bid(&circle_id, &member2, &45_000_000);
+        assert_eq!(client.get_leading_bid(&circle_id).unwrap().bidder, member2);
+
+        // Contributions complete while bidding is open: no payout yet
+        let members = [creator.clone(), member2.clone(), member3.clone()];
+        for member in members.iter() {
+            client.contribute(&circle_id, member);
+        }
+        assert_eq!(
+            client.try_process_payout(&circle_id).err(),
+            Some(Ok(CircleError::BiddingOpen))
+        );
+
+        env.ledger().set_timestamp(86400);
+        assert_eq!(
+            client.try_place_bid(&circle_id, &creator, &60_000_000).err(),
+            Some(Ok(CircleError::BiddingClosed))
+        );
+
+        // Winner takes the pot less their discount, the others split it
+        let payout = client.process_payout(&circle_id);
+        assert_eq!(payout.recipient, member2);
+        assert_eq!(payout.amount, 255_000_000);
+        assert_eq!(payout.discount, 45_000_000);
+        assert_eq!(token_client.balance(&member2), 10_155_000_000);
+        assert_eq!(token_client.balance(&creator), 9_922_500_000);
+        assert_eq!(token_client.balance(&member3), 9_922_500_000);
+
+        // The winner moves into round 1's position
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.members.get(0).unwrap(), member2);
+        assert_eq!(circle.members.get(1).unwrap(), creator);
+        assert_eq!(client.get_member(&circle_id, &member2).unwrap().payout_position, 1);
+        assert_eq!(client.get_member(&circle_id, &creator).unwrap().payout_position, 2);
+
+        // A member who has been paid can no longer bid
+        env.ledger().set_timestamp(86400 * 30);
+        assert_eq!(
+            client.try_place_bid(&circle_id, &member2, &10_000_000).err(),
+            Some(Ok(CircleError::PayoutAlreadyProcessed))
+        );
+
+        // Without bids the next member in order is paid in full once bidding closes
+        env.ledger().set_timestamp(86400 * 31);
+        for member in members.iter() {
+            client.contribute(&circle_id, member);
+        }
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.current_round, 3);
+        assert!(client.get_member(&circle_id, &creator).unwrap().has_received_payout);
+    }
+
+    #[test]
+    fn test_sealed_auction_payout() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.payout_mode = PayoutMode::SealedAuction;
+        config.bid_window = 86400;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let token_client = token::Client::new(&env, &token.address);
+
+        let seal = |discount: i128, salt: &BytesN<32>| {
+            let mut preimage = Bytes::from_array(&env, &discount.to_be_bytes());
+            preimage.append(&Bytes::from_array(&env, &salt.to_array()));
+            BytesN::from_array(&env, &env.crypto().sha256(&preimage).to_array())
+        };
+        let salt2 = BytesN::from_array(&env, &[2u8; 32]);
+        let salt3 = BytesN::from_array(&env, &[3u8; 32]);
+
+        client.commit_bid(&circle_id, &member2, &seal(20_000_000, &salt2));
+        client.commit_bid(&circle_id, &member3, &seal(40_000_000, &salt3));
+
+        // Sealed bids stay hidden until revealed
+        assert!(client.get_leading_bid(&circle_id).is_none());
+        assert_eq!(
+            client.try_reveal_bid(&circle_id, &member3, &40_000_000, &salt3).err(),
+            Some(Ok(CircleError::BiddingOpen))
+        );
+
+        env.ledger().set_timestamp(86400);
+        assert_eq!(
+            client.try_commit_bid(&circle_id, &creator, &seal(50_000_000, &salt2)).err(),
+            Some(Ok(CircleError::BiddingClosed))
+        );
+        assert_eq!(
+            client.try_reveal_bid(&circle_id, &member3, &50_000_000, &salt3).err(),
+            Some(Ok(CircleError::RevealMismatch))
+        );
+        client.reveal_bid(&circle_id, &member2, &20_000_000, &salt2);
+        client.reveal_bid(&circle_id, &member3, &40_000_000, &salt3);
+
+        // Contributions complete during the reveal window; payout waits for it to end
+        let members = [creator.clone(), member2.clone(), member3.clone()];
+        for member in members.iter() {
+            client.contribute(&circle_id, member);
+        }
+        assert_eq!(client.get_circle(&circle_id).unwrap().current_round, 1);
+
+        env.ledger().set_timestamp(86400 * 2);
+        let payout = client.process_payout(&circle_id);
+        assert_eq!(payout.recipient, member3);
+        assert_eq!(payout.amount, 260_000_000);
+        assert_eq!(token_client.balance(&member2), 9_920_000_000);
+        assert_eq!(token_client.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_random_payout_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 8;
+        config.payout_order = PayoutOrder::Random;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+
+        let mut joined = Vec::from_array(&env, [creator.clone()]);
+        for _ in 1..8 {
+            let member = Address::generate(&env);
+            client.join_circle(&invite_code, &member);
+            joined.push_back(member);
+        }
+
+        // Starting shuffles the members and renumbers their positions
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Active);
+        assert_eq!(circle.members.len(), 8);
+        assert_ne!(circle.members, joined);
+
+        for (index, member) in circle.members.iter().enumerate() {
+            assert!(joined.contains(&member));
+            let member_state = client.get_member(&circle_id, &member).unwrap();
+            assert_eq!(member_state.payout_position, index as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_credit_score_payout_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+        let reporter = Address::generate(&env);
+
+        let credit_id = env.register_contract(None, HaloCredit);
+        let credit = HaloCreditClient::new(&env, &credit_id);
+        credit.initialize(&admin);
+        credit.authorize_contract(&reporter);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.payout_order = PayoutOrder::CreditScore;
+
+        client.initialize(&admin, &identity, &credit_id);
+
+        // Each member joins in its own ledger so they get distinct unique IDs
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        env.ledger().set_sequence_number(1);
+        client.join_circle(&invite_code, &member2);
+
+        // member2 has a clean record, the creator a missed payment, member3 no record
+        let previous = BytesN::from_array(&env, &[9; 32]);
+        let creator_id = client.get_member(&circle_id, &creator).unwrap().unique_id;
+        let member2_id = client.get_member(&circle_id, &member2).unwrap().unique_id;
+        credit.record_payment(&reporter, &member2_id, &previous, &1, &token.address, &100_000_000, &true);
+        credit.record_payment(&reporter, &creator_id, &previous, &1, &token.address, &100_000_000, &true);
+        credit.record_missed_payment(&reporter, &creator_id, &previous, &2);
+        assert!(credit.get_score(&member2_id).unwrap() > credit.get_score(&creator_id).unwrap());
+
+        env.ledger().set_sequence_number(2);
+        client.join_circle(&invite_code, &member3);
+
+        // Starting orders the members by score, highest first
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Active);
+        assert_eq!(
+            circle.members,
+            Vec::from_array(&env, [member2.clone(), creator.clone(), member3.clone()])
+        );
+        assert_eq!(client.get_member(&circle_id, &member2).unwrap().payout_position, 1);
+        assert_eq!(client.get_member(&circle_id, &member3).unwrap().payout_position, 3);
+    }
+
+    #[test]
+    fn test_need_vote_payout() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.payout_order = PayoutOrder::NeedVote;
+
+        client.initialize(&admin, &identity, &credit);
+
+        // Need voting requires a voting window
+        assert_eq!(
+            client.try_create_circle(&creator, &config).err(),
+            Some(Ok(CircleError::InvalidConfig))
+        );
+        config.bid_window = 86400;
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+
+        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
+        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+
+        client.request_payout(&circle_id, &member2, &String::from_str(&env, "rent"));
+        client.request_payout(&circle_id, &member3, &String::from_str(&env, "medical bill"));
+
+        assert_eq!(
+            client.try_vote_need(&circle_id, &member3, &member3).err(),
+            Some(Ok(CircleError::InvalidVote))
+        );
+        assert_eq!(
+            client.try_vote_need(&circle_id, &member2, &creator).err(),
+            Some(Ok(CircleError::RequestNotFound))
+        );
+        assert_eq!(client.vote_need(&circle_id, &creator, &member3), 1);
+        assert_eq!(client.vote_need(&circle_id, &member2, &member3), 2);
+        assert_eq!(
+            client.try_vote_need(&circle_id, &member2, &member3).err(),
+            Some(Ok(CircleError::AlreadyVoted))
+        );
+
+        let members = [creator.clone(), member2.clone(), member3.clone()];
+        for member in members.iter() {
+            client.contribute(&circle_id, member);
+        }
+
+        // Payout waits for the voting window, then goes to the most-voted request
+        assert_eq!(
+            client.try_process_payout(&circle_id).err(),
+            Some(Ok(CircleError::BiddingOpen))
+        );
+        env.ledger().set_timestamp(86400);
+        assert_eq!(
+            client.try_request_payout(&circle_id, &creator, &String::from_str(&env, "late")).err(),
+            Some(Ok(CircleError::BiddingClosed))
+        );
+
+        let payout = client.process_payout(&circle_id);
+        assert_eq!(payout.recipient, member3);
+        assert_eq!(payout.amount, 300_000_000);
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.members.get(0).unwrap(), member3);
+        assert_eq!(circle.members.get(2).unwrap(), creator);
+        assert_eq!(client.get_member(&circle_id, &member3).unwrap().payout_position, 1);
+        assert_eq!(client.get_member(&circle_id, &creator).unwrap().payout_position, 3);
+    }
+
+    #[test]
+    fn test_swap_payout_positions() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let member4 = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 4;
+
+        client.initialize(&admin, &identity, &credit);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+        client.join_circle(&invite_code, &member4);
+
+        // The current round's position cannot be swapped
+        assert_eq!(
+            client.try_propose_swap(&circle_id, &member2, &2, &1).err(),
+            Some(Ok(CircleError::InvalidSwap))
+        );
+        // Members can only offer their own position
+        assert_eq!(
+            client.try_propose_swap(&circle_id, &member2, &3, &4).err(),
+            Some(Ok(CircleError::InvalidSwap))
+        );
+
+        client.propose_swap(&circle_id, &member4, &4, &2);
+        assert_eq!(
+            client.try_accept_swap(&circle_id, &member3, &member4).err(),
+            Some(Ok(CircleError::SwapNotFound))
+        );
 
-    fn count_contributions(env: &Env, circle_id: &BytesN<32>, state: &CircleState) -> u32 {
-        let mut count = 0u32;
-        for member in state.members.iter() {
-            if let Some(member_state) = env
-                .storage()
-                .persistent()
-                .get::<_, MemberState>(&DataKey::Member(circle_id.clone(), member))
-            {
-                if member_state.rounds_contributed.contains(&state.current_round) {
-                    count += 1;
-                }
+        // A competing proposal for the same position goes stale once the swap lands
+        client.propose_swap(&circle_id, &member3, &3, &2);
+        client.accept_swap(&circle_id, &member2, &member4);
+        assert!(client.get_swap_proposal(&circle_id, &member4).is_none());
+
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.members.get(1).unwrap(), member4);
+        assert_eq!(circle.members.get(3).unwrap(), member2);
+        assert_eq!(client.get_member(&circle_id, &member4).unwrap().payout_position, 2);
+        assert_eq!(client.get_member(&circle_id, &member2).unwrap().payout_position, 4);
+
+        assert_eq!(
+            client.try_accept_swap(&circle_id, &member4, &member3).err(),
+            Some(Ok(CircleError::SwapNotFound))
+        );
+        assert_eq!(
+            client.try_accept_swap(&circle_id, &member2, &member3).err(),
+            Some(Ok(CircleError::InvalidSwap))
+        );
+
+        // Round 2 now pays the member who swapped in
+        let members = [creator.clone(), member2.clone(), member3.clone(), member4.clone()];
+        for member in members.iter() {
+            token::StellarAssetClient::new(&env, &token.address).mint(member, &10_000_000_000);
+        }
+        for _ in 0..2 {
+            for member in members.iter() {
+                client.contribute(&circle_id, member);
             }
         }
-        count
+        assert!(client.get_member(&circle_id, &member4).unwrap().has_received_payout);
+        assert!(!client.get_member(&circle_id, &member2).unwrap().has_received_payout);
     }
 
-    fn try_process_payout(env: &Env, circle_id: BytesN<32>) -> Result<(), CircleError> {
-        let state: CircleState = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Circle(circle_id.clone()))
-            .ok_or(CircleError::CircleNotFound)?;
+    #[test]
+    fn test_sell_payout_position() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        let contributions = Self::count_contributions(env, &circle_id, &state);
+        let contract_id = env.register_contract(None, HaloCircle);
+        let client = HaloCircleClient::new(&env, &contract_id);
 
-        if contributions == state.config.total_members {
-            // All contributed, process payout
-            // Note: This calls process_payout internally
-            // In production, you might want a separate admin trigger
-        }
+        let admin = Address::generate(&env);
+        let identity = Address::generate(&env);
+        let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let buyer = Address::generate(&env);
+        let token_admin = Address::generate(&env);
 
-        Ok(())
-    }
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.deposit = DepositRequirement::Multiple(1);
 
-    fn record_payment_to_credit(
-        env: &Env,
-        unique_id: &BytesN<32>,
-        circle_id: &BytesN<32>,
-        round: u32,
-        amount: i128,
-        on_time: bool,
-    ) {
-        #[cfg(feature = "cross-contract")]
-        {
-            // Get the Credit contract address
-            if let Some(credit_contract) = env
-                .storage()
-                .instance()
-                .get::<_, Address>(&DataKey::CreditContract)
-            {
-                // Create client for Credit contract
-                let client = credit_client::Client::new(env, &credit_contract);
+        client.initialize(&admin, &identity, &credit);
 
-                // Record payment (Circle contract must be authorized in Credit contract)
-                // Ignore errors - payment recording is not critical for circle operation
-                let _ = client.record_payment(
-                    &env.current_contract_address(),
-                    unique_id,
-                    circle_id,
-                    &round,
-                    &amount,
-                    &on_time,
-                );
-            }
+        for member in [&creator, &member2, &member3, &buyer] {
+            token::StellarAssetClient::new(&env, &token.address).mint(member, &10_000_000_000);
         }
+        let token_client = token::Client::new(&env, &token.address);
 
-        // When cross-contract feature is disabled, do nothing
-        #[cfg(not(feature = "cross-contract"))]
-        let _ = (env, unique_id, circle_id, round, amount, on_time);
-    }
-
-    fn finalize_circle(
-        env: &Env,
-        circle_id: &BytesN<32>,
-        state: &CircleState,
-    ) -> Result<(), CircleError> {
-        #[cfg(feature = "cross-contract")]
-        {
-            // Get the Credit contract address
-            if let Some(credit_contract) = env
-                .storage()
-                .instance()
-                .get::<_, Address>(&DataKey::CreditContract)
-            {
-                // Create client for Credit contract
-                let client = credit_client::Client::new(env, &credit_contract);
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
 
-                // Record circle completion for all members
-                for member in state.members.iter() {
-                    if let Some(member_state) = env
-                        .storage()
-                        .persistent()
-                        .get::<_, MemberState>(&DataKey::Member(circle_id.clone(), member))
-                    {
-                        // Record successful completion (ignore errors)
-                        let _ = client.record_circle_completion(
-                            &env.current_contract_address(),
-                            &member_state.unique_id,
-                            circle_id,
-                            &true, // completed successfully
-                        );
-                    }
-                }
-            }
+        for member in [&creator, &member2, &member3] {
+            client.contribute(&circle_id, member);
         }
 
-        // When cross-contract feature is disabled, just emit event
-        #[cfg(not(feature = "cross-contract"))]
-        let _ = state.members.iter();
-
-        env.events().publish(
-            (Symbol::new(&env, "circle_completed"),),
-            (circle_id.clone(), state.total_contributed, state.total_paid_out),
+        // Only future, unpaid slots held by non-creators can be listed
+        assert_eq!(
+            client.try_list_position(&circle_id, &member2, &50_000_000).err(),
+            Some(Ok(CircleError::PositionNotTransferable))
+        );
+        assert_eq!(
+            client.try_list_position(&circle_id, &creator, &50_000_000).err(),
+            Some(Ok(CircleError::PositionNotTransferable))
         );
 
-        Ok(())
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger};
-    use soroban_sdk::{token, Env};
-
-    fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
-        let contract_id = env.register_stellar_asset_contract_v2(admin.clone());
-        token::Client::new(env, &contract_id.address())
-    }
-
-    fn create_config(env: &Env, token: &Address) -> CircleConfig {
-        CircleConfig {
-            name: Symbol::new(env, "TestCircle"),
-            contribution_amount: 100_000_000, // 100 USDC (6 decimals)
-            contribution_token: token.clone(),
-            total_members: 3,
-            period_length: 86400 * 30, // 30 days
-            grace_period: 86400 * 7,   // 7 days
-            late_fee_percent: 5,
+        client.contribute(&circle_id, &member3);
+        client.list_position(&circle_id, &member3, &50_000_000);
+        assert_eq!(
+            client.try_buy_position(&circle_id, &member2, &member3).err(),
+            Some(Ok(CircleError::AlreadyMember))
+        );
+        assert_eq!(client.buy_position(&circle_id, &buyer, &member3), 3);
+
+        // Seller is paid, gets their deposit back and leaves
+        assert_eq!(token_client.balance(&member3), 9_850_000_000);
+        assert_eq!(token_client.balance(&buyer), 9_850_000_000);
+        assert!(client.get_member(&circle_id, &member3).is_none());
+        assert!(client.get_listing(&circle_id, &member3).is_none());
+
+        // The buyer starts a fresh record; only the seller's payment into the
+        // current round stays with the slot
+        let slot = client.get_member(&circle_id, &buyer).unwrap();
+        assert_eq!(slot.payout_position, 3);
+        assert_eq!(slot.deposit, 100_000_000);
+        assert_eq!(slot.total_contributed, 0);
+        assert_eq!(slot.rounds_contributed, Vec::from_array(&env, [2u32]));
+        assert_eq!(client.get_circle(&circle_id).unwrap().members.get(2).unwrap(), buyer);
+
+        // The buyer carries the remaining contributions and receives the final payout
+        assert_eq!(
+            client.try_contribute(&circle_id, &member3).err(),
+            Some(Ok(CircleError::NotMember))
+        );
+        client.contribute(&circle_id, &creator);
+        client.contribute(&circle_id, &member2);
+        for member in [&creator, &member2, &buyer] {
+            client.contribute(&circle_id, member);
         }
+        assert_eq!(client.get_circle(&circle_id).unwrap().status, CircleStatus::Completed);
+        assert_eq!(client.get_member(&circle_id, &buyer).unwrap().total_contributed, 100_000_000);
+        assert_eq!(token_client.balance(&buyer), 10_150_000_000);
+        assert_eq!(token_client.balance(&contract_id), 0);
     }
 
     #[test]
-    fn test_initialize() {
+    fn test_sold_position_drops_need_votes() {
         let env = Env::default();
+        env.mock_all_auths();
+
         let contract_id = env.register_contract(None, HaloCircle);
         let client = HaloCircleClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
         let identity = Address::generate(&env);
         let credit = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let seller = Address::generate(&env);
+        let buyer = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+
+        let token = create_token(&env, &token_admin);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 4;
+        config.payout_order = PayoutOrder::NeedVote;
+        config.bid_window = 86400;
 
         client.initialize(&admin, &identity, &credit);
 
-        assert_eq!(client.get_admin(), admin);
-        assert_eq!(client.get_circle_count(), 0);
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+        client.join_circle(&invite_code, &seller);
+
+        // The seller votes for member3 and collects a vote for their own request
+        client.request_payout(&circle_id, &member3, &String::from_str(&env, "rent"));
+        client.request_payout(&circle_id, &seller, &String::from_str(&env, "tuition"));
+        client.vote_need(&circle_id, &seller, &member3);
+        client.vote_need(&circle_id, &creator, &seller);
+
+        client.list_position(&circle_id, &seller, &0);
+        client.buy_position(&circle_id, &buyer, &seller);
+
+        // The seller's vote and request go with them, freeing the creator's vote
+        assert_eq!(client.get_need_request(&circle_id, &1, &member3).unwrap().votes, 0);
+        assert!(client.get_need_request(&circle_id, &1, &seller).is_none());
+        assert_eq!(client.vote_need(&circle_id, &creator, &member3), 1);
+        assert_eq!(client.vote_need(&circle_id, &buyer, &member3), 2);
     }
 
     #[test]
-    fn test_create_circle() {
+    fn test_sold_position_drops_bid() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -1089,23 +3991,39 @@ mod test {
         let identity = Address::generate(&env);
         let credit = Address::generate(&env);
         let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let seller = Address::generate(&env);
+        let buyer = Address::generate(&env);
         let token_admin = Address::generate(&env);
 
         let token = create_token(&env, &token_admin);
-        let config = create_config(&env, &token.address);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 4;
+        config.payout_mode = PayoutMode::OpenAuction;
+        config.bid_window = 86400;
 
         client.initialize(&admin, &identity, &credit);
 
         let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+        client.join_circle(&invite_code, &seller);
 
-        let circle = client.get_circle(&circle_id).unwrap();
-        assert_eq!(circle.status, CircleStatus::Forming);
-        assert_eq!(circle.members.len(), 1); // Creator is first member
-        assert_eq!(client.get_circle_count(), 1);
+        client.place_bid(&circle_id, &seller, &50_000_000);
+        client.list_position(&circle_id, &seller, &0);
+        client.buy_position(&circle_id, &buyer, &seller);
+
+        // The seller's bid leaves with them and no longer sets the bar
+        assert!(client.get_bid(&circle_id, &1, &seller).is_none());
+        assert!(client.get_leading_bid(&circle_id).is_none());
+        client.place_bid(&circle_id, &buyer, &10_000_000);
+        assert_eq!(client.get_leading_bid(&circle_id).unwrap().bidder, buyer);
     }
 
     #[test]
-    fn test_join_circle() {
+    fn test_leave_and_remove_forming_members() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -1118,35 +4036,59 @@ mod test {
         let creator = Address::generate(&env);
         let member2 = Address::generate(&env);
         let member3 = Address::generate(&env);
+        let member4 = Address::generate(&env);
         let token_admin = Address::generate(&env);
 
         let token = create_token(&env, &token_admin);
-        let config = create_config(&env, &token.address);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 5;
+        config.deposit = DepositRequirement::Fixed(20_000_000);
 
         client.initialize(&admin, &identity, &credit);
 
-        let circle_id = client.create_circle(&creator, &config);
-
-        // Get invite code
-        let circle = client.get_circle(&circle_id).unwrap();
-        let invite_code = circle.invite_code;
+        for member in [&creator, &member2, &member3, &member4] {
+            token::StellarAssetClient::new(&env, &token.address).mint(member, &1_000_000_000);
+        }
+        let token_client = token::Client::new(&env, &token.address);
 
-        // Join with member 2
-        let position2 = client.join_circle(&invite_code, &member2);
-        assert_eq!(position2, 2);
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
+        client.join_circle(&invite_code, &member3);
+        client.join_circle(&invite_code, &member4);
 
-        // Join with member 3 - circle should auto-start
-        let position3 = client.join_circle(&invite_code, &member3);
-        assert_eq!(position3, 3);
+        // Leaving refunds the deposit and closes the gap
+        client.leave_circle(&circle_id, &member2);
+        assert_eq!(token_client.balance(&member2), 1_000_000_000);
+        assert!(client.get_member(&circle_id, &member2).is_none());
 
-        // Circle should now be active
         let circle = client.get_circle(&circle_id).unwrap();
-        assert_eq!(circle.status, CircleStatus::Active);
-        assert_eq!(circle.current_round, 1);
+        let remaining = Vec::from_array(&env, [creator.clone(), member3.clone(), member4.clone()]);
+        assert_eq!(circle.members, remaining);
+        assert_eq!(client.get_member(&circle_id, &member3).unwrap().payout_position, 2);
+        assert_eq!(client.get_member(&circle_id, &member4).unwrap().payout_position, 3);
+
+        // The creator can remove members but not leave
+        assert_eq!(
+            client.try_leave_circle(&circle_id, &creator).err(),
+            Some(Ok(CircleError::Unauthorized))
+        );
+        client.remove_member(&circle_id, &member3, &String::from_str(&env, "no response"));
+        assert_eq!(token_client.balance(&member3), 1_000_000_000);
+        assert_eq!(client.get_member(&circle_id, &member4).unwrap().payout_position, 2);
+        assert_eq!(
+            client.try_leave_circle(&circle_id, &member3).err(),
+            Some(Ok(CircleError::NotMember))
+        );
+
+        // The freed places can be filled again
+        client.join_circle(&invite_code, &member2);
+        assert_eq!(client.get_member(&circle_id, &member2).unwrap().payout_position, 3);
+        assert_eq!(token_client.balance(&contract_id), 60_000_000);
     }
 
     #[test]
-    fn test_contribute() {
+    fn test_expire_forming_circle() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -1162,37 +4104,88 @@ mod test {
         let token_admin = Address::generate(&env);
 
         let token = create_token(&env, &token_admin);
-        let config = create_config(&env, &token.address);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 5;
+        config.forming_deadline = 86400 * 7;
+        config.deposit = DepositRequirement::Fixed(20_000_000);
 
         client.initialize(&admin, &identity, &credit);
 
-        let circle_id = client.create_circle(&creator, &config);
-        let circle = client.get_circle(&circle_id).unwrap();
-        let invite_code = circle.invite_code;
+        for member in [&creator, &member2, &member3] {
+            token::StellarAssetClient::new(&env, &token.address).mint(member, &1_000_000_000);
+        }
+        let token_client = token::Client::new(&env, &token.address);
+
+        // Below the minimum: cancelled and refunded
+        let short = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&short).unwrap().invite_code;
+        client.join_circle(&invite_code, &member2);
 
+        // Enough members: starts with the smaller group
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
         client.join_circle(&invite_code, &member2);
         client.join_circle(&invite_code, &member3);
 
-        // Mint tokens to members
-        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &1_000_000_000);
-        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &1_000_000_000);
-        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &1_000_000_000);
+        assert_eq!(
+            client.try_expire_circle(&circle_id).err(),
+            Some(Ok(CircleError::DeadlineNotReached))
+        );
 
-        // Make contributions
-        let record1 = client.contribute(&circle_id, &creator);
-        assert!(record1.on_time);
+        // A deposit multiple must fit the smallest group that can start
+        let mut oversized = config.clone();
+        oversized.deposit = DepositRequirement::Multiple(4);
+        assert_eq!(
+            client.try_create_circle(&creator, &oversized).err(),
+            Some(Ok(CircleError::InvalidConfig))
+        );
+        oversized.forming_deadline = 0;
+        let oversized_id = client.create_circle(&creator, &oversized);
+        let oversized_code = client.get_circle(&oversized_id).unwrap().invite_code;
+        client.join_circle(&oversized_code, &member2);
+        client.join_circle(&oversized_code, &member3);
+        assert_eq!(
+            client.try_start_circle(&oversized_id).err(),
+            Some(Ok(CircleError::InvalidConfig))
+        );
+        client.cancel_circle(&oversized_id);
 
-        let record2 = client.contribute(&circle_id, &member2);
-        assert!(record2.on_time);
+        // Joins close at the deadline, even before the circle is expired
+        env.ledger().set_timestamp(86400 * 7 + 1);
+        assert_eq!(
+            client.try_join_circle(&invite_code, &Address::generate(&env)).err(),
+            Some(Ok(CircleError::FormingDeadlinePassed))
+        );
+        assert_eq!(client.expire_circle(&short), CircleStatus::Cancelled);
+        assert_eq!(client.get_circle(&short).unwrap().status, CircleStatus::Cancelled);
+        assert_eq!(client.get_member(&short, &member2).unwrap().deposit, 0);
+
+        assert_eq!(client.expire_circle(&circle_id), CircleStatus::Active);
+        assert_eq!(
+            client.try_expire_circle(&circle_id).err(),
+            Some(Ok(CircleError::CircleNotForming))
+        );
 
-        // Check contribution status
-        let (contributed, total) = client.get_contribution_status(&circle_id);
-        assert_eq!(contributed, 2);
-        assert_eq!(total, 3);
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.config.total_members, 3);
+        assert_eq!(circle.started_at, 86400 * 7 + 1);
+
+        // Three rounds with a three-member pot
+        for _ in 0..3 {
+            for member in [&creator, &member2, &member3] {
+                client.contribute(&circle_id, member);
+            }
+        }
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.status, CircleStatus::Completed);
+        assert_eq!(circle.total_paid_out, 900_000_000);
+        for member in [&creator, &member2, &member3] {
+            assert_eq!(token_client.balance(member), 1_000_000_000);
+        }
     }
 
     #[test]
-    fn test_full_circle_lifecycle() {
+    fn test_auction_circle_started_short() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -1208,36 +4201,78 @@ mod test {
         let token_admin = Address::generate(&env);
 
         let token = create_token(&env, &token_admin);
-        let config = create_config(&env, &token.address);
+        let mut config = create_config(&env, &token.address);
+        config.total_members = 5;
+        config.payout_mode = PayoutMode::OpenAuction;
+        config.bid_window = 86400;
+        config.deposit = DepositRequirement::Multiple(1);
 
         client.initialize(&admin, &identity, &credit);
 
-        // Create and fill circle
-        let circle_id = client.create_circle(&creator, &config);
-        let circle = client.get_circle(&circle_id).unwrap();
-        let invite_code = circle.invite_code;
+        // Without a deadline the minimum is not checked
+        let mut open = create_config(&env, &token.address);
+        open.min_members = 0;
+        client.create_circle(&creator, &open);
+        open.forming_deadline = 86400 * 7;
+        assert_eq!(
+            client.try_create_circle(&creator, &open).err(),
+            Some(Ok(CircleError::InvalidConfig))
+        );
+        config.forming_deadline = 86400 * 7;
 
+        for member in [&creator, &member2, &member3] {
+            token::StellarAssetClient::new(&env, &token.address).mint(member, &1_000_000_000);
+        }
+        let token_client = token::Client::new(&env, &token.address);
+
+        let circle_id = client.create_circle(&creator, &config);
+        let invite_code = client.get_circle(&circle_id).unwrap().invite_code;
         client.join_circle(&invite_code, &member2);
         client.join_circle(&invite_code, &member3);
 
-        // Mint tokens
-        token::StellarAssetClient::new(&env, &token.address).mint(&creator, &10_000_000_000);
-        token::StellarAssetClient::new(&env, &token.address).mint(&member2, &10_000_000_000);
-        token::StellarAssetClient::new(&env, &token.address).mint(&member3, &10_000_000_000);
+        let start = 86400 * 7 + 1;
+        env.ledger().set_timestamp(start);
+        assert_eq!(client.expire_circle(&circle_id), CircleStatus::Active);
+        assert_eq!(token_client.balance(&contract_id), 300_000_000);
 
-        // Round 1: All contribute, creator receives payout
-        client.contribute(&circle_id, &creator);
-        client.contribute(&circle_id, &member2);
-        client.contribute(&circle_id, &member3);
+        // Bids are capped by the three-member pot
+        assert_eq!(
+            client.try_place_bid(&circle_id, &member3, &300_000_000).err(),
+            Some(Ok(CircleError::InvalidBid))
+        );
+        client.place_bid(&circle_id, &member3, &30_000_000);
+        for member in [&creator, &member2, &member3] {
+            client.contribute(&circle_id, member);
+        }
 
-        // Process payout
-        let payout1 = client.process_payout(&circle_id);
-        assert_eq!(payout1.round, 1);
-        assert_eq!(payout1.recipient, creator);
+        // The discount is split between the two other members
+        env.ledger().set_timestamp(start + 86400);
+        let payout = client.process_payout(&circle_id);
+        assert_eq!(payout.recipient, member3);
+        assert_eq!(payout.amount, 270_000_000);
+        assert_eq!(token_client.balance(&creator), 815_000_000);
+        assert_eq!(token_client.balance(&member2), 815_000_000);
+
+        // Two more rounds, then the circle completes and releases the deposits
+        env.ledger().set_timestamp(start + 86400 * 31);
+        for member in [&creator, &member2, &member3] {
+            client.contribute(&circle_id, member);
+        }
+        env.ledger().set_timestamp(start + 86400 * 60);
+        for member in [&creator, &member2, &member3] {
+            client.contribute(&circle_id, member);
+        }
 
-        // Check circle advanced to round 2
         let circle = client.get_circle(&circle_id).unwrap();
-        assert_eq!(circle.current_round, 2);
+        assert_eq!(circle.status, CircleStatus::Completed);
+        assert_eq!(circle.current_round, 4);
+        assert_eq!(circle.total_paid_out, 900_000_000);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert_eq!(token_client.balance(&member3), 970_000_000);
+        assert_eq!(
+            token_client.balance(&creator) + token_client.balance(&member2),
+            2_030_000_000
+        );
     }
 
     #[test]
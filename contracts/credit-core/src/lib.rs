@@ -0,0 +1,389 @@
+//! Halo Protocol Credit Scoring Core
+//!
+//! The scoring model used by the Credit contract, with no Soroban dependency so
+//! it compiles for both wasm contracts and native services. Off-chain code that
+//! links this crate computes exactly the same scores as the contract.
+//!
+//! Scores range from 300-850 and are calculated based on:
+//! - Payment History (40%) - On-time vs late payments (circle and loan installments)
+//! - Circle Completion (25%) - Successfully completed circles and repaid loans
+//! - Volume (15%) - Total transaction volume in reference units (logarithmic scale)
+//! - Tenure (10%) - Time since first activity
+//! - Peer Attestation (10%) - Reserved for future vouching system
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+/// Base score for new users
+pub const BASE_SCORE: u32 = 300;
+/// Maximum possible score
+pub const MAX_SCORE: u32 = 850;
+/// Current score algorithm version
+pub const SCORE_VERSION: u32 = 2;
+/// Decimals of the reference unit volume is normalised to (USDC)
+pub const REFERENCE_DECIMALS: u32 = 6;
+
+/// Weight for payment history (40% = 220 points max)
+pub const PAYMENT_HISTORY_MAX: u32 = 220;
+/// Weight for circle completion (25% = 137 points max)
+pub const CIRCLE_COMPLETION_MAX: u32 = 137;
+/// Weight for volume (15% = 83 points max)
+pub const VOLUME_MAX: u32 = 83;
+/// Weight for tenure (10% = 55 points max)
+pub const TENURE_MAX: u32 = 55;
+/// Weight for attestation (10% = 55 points max)
+pub const ATTESTATION_MAX: u32 = 55;
+
+/// Loan installments count this many times a circle payment in payment history
+pub const LOAN_PAYMENT_WEIGHT: u32 = 2;
+/// Payment history penalty per charged-off loan
+pub const CHARGE_OFF_PENALTY: u32 = 100;
+/// Maximum payment history penalty from charged-off loans
+pub const CHARGE_OFF_PENALTY_MAX: u32 = 200;
+
+/// Counters the score is computed from
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ScoreInputs {
+    /// Total number of circle payments (including missed)
+    pub total_payments: u32,
+    /// Number of on-time circle payments
+    pub on_time_payments: u32,
+    /// Number of missed circle payments
+    pub missed_payments: u32,
+    /// Number of circles completed successfully
+    pub circles_completed: u32,
+    /// Number of circles defaulted/failed
+    pub circles_defaulted: u32,
+    /// Total volume transacted (in reference units)
+    pub total_volume: i128,
+    /// Timestamp of first activity
+    pub first_activity: u64,
+    /// Number of loan installments paid on time
+    pub loan_payments_on_time: u32,
+    /// Number of loan installments paid late
+    pub loan_payments_late: u32,
+    /// Number of loans repaid in full
+    pub loans_repaid: u32,
+    /// Number of loans charged off
+    pub loans_charged_off: u32,
+}
+
+/// Score breakdown by component
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Breakdown {
+    /// Payment history component (max 220)
+    pub payment_history: u32,
+    /// Circle completion component (max 137)
+    pub circle_completion: u32,
+    /// Volume component (max 83)
+    pub volume: u32,
+    /// Tenure component (max 55)
+    pub tenure: u32,
+    /// Attestation component (max 55)
+    pub attestation: u32,
+    /// Total score
+    pub total: u32,
+}
+
+/// Score tier based on credit score
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Tier {
+    /// 300-449: Building credit
+    Building = 0,
+    /// 450-599: Fair
+    Fair = 1,
+    /// 600-749: Good
+    Good = 2,
+    /// 750-850: Excellent
+    Excellent = 3,
+}
+
+/// Calculate the score breakdown for `inputs` as of `now` (unix seconds).
+pub fn calculate_breakdown(inputs: &ScoreInputs, now: u64) -> Breakdown {
+    // 1. Payment History (40% = max 220 points)
+    // Loan installments are weighted above circle payments
+    let loan_payments = inputs.loan_payments_on_time + inputs.loan_payments_late;
+    let payment_score = if inputs.total_payments > 0 || loan_payments > 0 {
+        // Include missed payments in calculation
+        let total = inputs.total_payments + loan_payments * LOAN_PAYMENT_WEIGHT;
+        let on_time = inputs.on_time_payments + inputs.loan_payments_on_time * LOAN_PAYMENT_WEIGHT;
+        let on_time_ratio = (on_time * 100) / total;
+
+        // Missed payments have severe penalty
+        let missed_penalty = if inputs.missed_payments > 0 {
+            (inputs.missed_payments * 30).min(100) // -30 points per missed, max -100
+        } else {
+            0
+        };
+
+        // Charged-off loans carry their own penalty
+        let charge_off_penalty =
+            (inputs.loans_charged_off * CHARGE_OFF_PENALTY).min(CHARGE_OFF_PENALTY_MAX);
+
+        let base_payment_score = (on_time_ratio * PAYMENT_HISTORY_MAX) / 100;
+        base_payment_score.saturating_sub(missed_penalty + charge_off_penalty)
+    } else {
+        PAYMENT_HISTORY_MAX / 2 // Neutral for no history
+    };
+
+    // 2. Circle Completion (25% = max 137 points)
+    // Repaid loans count as completions, charged-off loans as defaults
+    let completed = inputs.circles_completed + inputs.loans_repaid;
+    let total_circles = completed + inputs.circles_defaulted + inputs.loans_charged_off;
+    let completion_score = if total_circles > 0 {
+        let completion_ratio = (completed * 100) / total_circles;
+        (completion_ratio * CIRCLE_COMPLETION_MAX) / 100
+    } else {
+        CIRCLE_COMPLETION_MAX / 2 // Neutral
+    };
+
+    // 3. Volume (15% = max 83 points) - Logarithmic scale
+    let volume_score = calculate_volume_score(inputs.total_volume);
+
+    // 4. Tenure (10% = max 55 points)
+    let tenure_days = now.saturating_sub(inputs.first_activity) / 86400;
+    let tenure_score = if tenure_days > 365 {
+        TENURE_MAX
+    } else {
+        ((tenure_days as u32) * TENURE_MAX) / 365
+    };
+
+    // 5. Attestation (10% = max 55 points) - Neutral for now
+    let attestation_score = ATTESTATION_MAX / 2;
+
+    // Calculate total (base 300)
+    let total = (BASE_SCORE
+        + payment_score
+        + completion_score
+        + volume_score
+        + tenure_score
+        + attestation_score)
+        .min(MAX_SCORE);
+
+    Breakdown {
+        payment_history: payment_score,
+        circle_completion: completion_score,
+        volume: volume_score,
+        tenure: tenure_score,
+        attestation: attestation_score,
+        total,
+    }
+}
+
+/// Volume component for a volume in reference units.
+pub fn calculate_volume_score(volume: i128) -> u32 {
+    // Logarithmic scale for volume (reference units have 6 decimal places, like USDC)
+    // $100 = 20%, $1000 = 40%, $10000 = 60%, $100000 = 80%, $1M+ = 100%
+    let percentage = if volume < 100_000_000 {
+        // < $100
+        20
+    } else if volume < 1_000_000_000 {
+        // < $1000
+        40
+    } else if volume < 10_000_000_000 {
+        // < $10000
+        60
+    } else if volume < 100_000_000_000 {
+        // < $100000
+        80
+    } else {
+        100
+    };
+
+    (percentage * VOLUME_MAX) / 100
+}
+
+/// Tier for a score.
+pub fn score_to_tier(score: u32) -> Tier {
+    if score < 450 {
+        Tier::Building
+    } else if score < 600 {
+        Tier::Fair
+    } else if score < 750 {
+        Tier::Good
+    } else {
+        Tier::Excellent
+    }
+}
+
+/// Convert a token amount to reference units.
+/// `price` is the value of one whole token in reference units.
+pub fn normalize_amount(amount: i128, decimals: u32, price: i128) -> i128 {
+    amount.saturating_mul(price) / 10i128.pow(decimals)
+}
+
+/// Depth of the Merkle tree of score certificate commitments
+pub const CERTIFICATE_TREE_DEPTH: u32 = 20;
+/// Length of a certificate commitment preimage
+pub const CERTIFICATE_PREIMAGE_LEN: usize = 100;
+
+/// Fields committed to by a score certificate
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CertificateFields {
+    /// Audience-specific pseudonym of the unique ID
+    pub pseudonym: [u8; 32],
+    /// Audience the certificate was issued for
+    pub audience: [u8; 32],
+    /// Credit score
+    pub score: u32,
+    /// Score tier
+    pub tier: Tier,
+    /// Score algorithm version
+    pub score_version: u32,
+    /// Ledger sequence the certificate was issued at
+    pub ledger: u32,
+    /// Timestamp the certificate was issued at
+    pub issued_at: u64,
+    /// Timestamp the certificate expires at
+    pub expires_at: u64,
+    /// Position of the certificate in the commitment tree
+    pub index: u32,
+}
+
+impl CertificateFields {
+    /// Canonical encoding hashed (SHA-256) into the certificate commitment.
+    /// Fixed-size fields in declaration order, integers big-endian.
+    pub fn preimage(&self) -> [u8; CERTIFICATE_PREIMAGE_LEN] {
+        let mut out = [0u8; CERTIFICATE_PREIMAGE_LEN];
+        out[0..32].copy_from_slice(&self.pseudonym);
+        out[32..64].copy_from_slice(&self.audience);
+        out[64..68].copy_from_slice(&self.score.to_be_bytes());
+        out[68..72].copy_from_slice(&(self.tier as u32).to_be_bytes());
+        out[72..76].copy_from_slice(&self.score_version.to_be_bytes());
+        out[76..80].copy_from_slice(&self.ledger.to_be_bytes());
+        out[80..88].copy_from_slice(&self.issued_at.to_be_bytes());
+        out[88..96].copy_from_slice(&self.expires_at.to_be_bytes());
+        out[96..100].copy_from_slice(&self.index.to_be_bytes());
+        out
+    }
+}
+
+/// Encoding hashed (SHA-256) into the pseudonym of `unique_id` for `audience`.
+/// Different audiences see unlinkable pseudonyms for the same user.
+pub fn pseudonym_preimage(unique_id: &[u8; 32], audience: &[u8; 32]) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    out[0..32].copy_from_slice(unique_id);
+    out[32..64].copy_from_slice(audience);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn inputs() -> impl Strategy<Value = ScoreInputs> {
+        (
+            (0u32..1000, 0u32..1000, 0u32..50),
+            (0u32..50, 0u32..50),
+            0i128..1_000_000_000_000,
+            (0u32..200, 0u32..200, 0u32..20, 0u32..20),
+        )
+            .prop_map(|((on_time, late, missed), (completed, defaulted), volume, loans)| {
+                ScoreInputs {
+                    total_payments: on_time + late + missed,
+                    on_time_payments: on_time,
+                    missed_payments: missed,
+                    circles_completed: completed,
+                    circles_defaulted: defaulted,
+                    total_volume: volume,
+                    first_activity: 0,
+                    loan_payments_on_time: loans.0,
+                    loan_payments_late: loans.1,
+                    loans_repaid: loans.2,
+                    loans_charged_off: loans.3,
+                }
+            })
+    }
+
+    #[test]
+    fn test_new_user_breakdown() {
+        let breakdown = calculate_breakdown(&ScoreInputs::default(), 0);
+
+        assert_eq!(breakdown.payment_history, 110);
+        assert_eq!(breakdown.circle_completion, 68);
+        assert_eq!(breakdown.volume, 16);
+        assert_eq!(breakdown.tenure, 0);
+        assert_eq!(breakdown.attestation, 27);
+        assert_eq!(breakdown.total, 521);
+        assert_eq!(score_to_tier(breakdown.total), Tier::Fair);
+    }
+
+    #[test]
+    fn test_normalize_amount() {
+        // 100 USDC at $1
+        assert_eq!(normalize_amount(100_000_000, 6, 1_000_000), 100_000_000);
+        // 1000 units of a 7-decimal token at $0.10
+        assert_eq!(normalize_amount(10_000_000_000, 7, 100_000), 100_000_000);
+    }
+
+    #[test]
+    fn test_certificate_preimage() {
+        let fields = CertificateFields {
+            pseudonym: [1; 32],
+            audience: [2; 32],
+            score: 700,
+            tier: Tier::Good,
+            score_version: 1,
+            ledger: 42,
+            issued_at: 1_000,
+            expires_at: 2_000,
+            index: 3,
+        };
+        let preimage = fields.preimage();
+
+        assert_eq!(&preimage[0..32], &[1; 32]);
+        assert_eq!(&preimage[32..64], &[2; 32]);
+        assert_eq!(&preimage[64..68], &700u32.to_be_bytes());
+        assert_eq!(&preimage[68..72], &2u32.to_be_bytes());
+        assert_eq!(&preimage[96..100], &3u32.to_be_bytes());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_score_in_range(inputs in inputs(), days in 0u64..2000) {
+            let breakdown = calculate_breakdown(&inputs, days * 86400);
+
+            prop_assert!(breakdown.total >= BASE_SCORE && breakdown.total <= MAX_SCORE);
+            prop_assert!(breakdown.payment_history <= PAYMENT_HISTORY_MAX);
+            prop_assert!(breakdown.circle_completion <= CIRCLE_COMPLETION_MAX);
+            prop_assert!(breakdown.volume <= VOLUME_MAX);
+            prop_assert!(breakdown.tenure <= TENURE_MAX);
+        }
+
+        #[test]
+        fn prop_on_time_payment_never_lowers_score(inputs in inputs(), days in 0u64..2000) {
+            let mut better = inputs;
+            better.total_payments += 1;
+            better.on_time_payments += 1;
+
+            let now = days * 86400;
+            prop_assert!(
+                calculate_breakdown(&better, now).total >= calculate_breakdown(&inputs, now).total
+            );
+        }
+
+        #[test]
+        fn prop_missed_payment_never_raises_score(inputs in inputs(), days in 0u64..2000) {
+            let mut worse = inputs;
+            worse.total_payments += 1;
+            worse.missed_payments += 1;
+
+            let now = days * 86400;
+            prop_assert!(
+                calculate_breakdown(&worse, now).total <= calculate_breakdown(&inputs, now).total
+            );
+        }
+
+        #[test]
+        fn prop_volume_score_monotonic(a in 0i128..1_000_000_000_000, b in 0i128..1_000_000_000_000) {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            prop_assert!(calculate_volume_score(low) <= calculate_volume_score(high));
+        }
+
+        #[test]
+        fn prop_tier_monotonic(a in BASE_SCORE..=MAX_SCORE, b in BASE_SCORE..=MAX_SCORE) {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            prop_assert!(score_to_tier(low) <= score_to_tier(high));
+        }
+    }
+}
@@ -2,25 +2,49 @@
 //!
 //! This contract manages on-chain credit scores for Halo Protocol users.
 //! Scores range from 300-850 and are calculated based on:
-//! - Payment History (40%) - On-time vs late payments
-//! - Circle Completion (25%) - Successfully completed circles
-//! - Volume (15%) - Total transaction volume (logarithmic scale)
+//! - Payment History (40%) - On-time vs late payments (circle and loan installments)
+//! - Circle Completion (25%) - Successfully completed circles and repaid loans
+//! - Volume (15%) - Total transaction volume normalised across tokens (logarithmic scale)
 //! - Tenure (10%) - Time since first activity
 //! - Peer Attestation (10%) - Reserved for future vouching system
 //!
+//! The scoring model itself lives in `halo-credit-core` so off-chain services
+//! compute identical scores.
+//!
 //! Key Features:
 //! - Authorized contracts (Circle) can record payments
+//! - Authorized lenders can report loan repayment events
 //! - Public query functions for SDK integration
+//! - User consent registry for relying-party access
+//! - Prepaid, per-query fees for consented queries
 //! - Score decay for inactive users
 //! - Full payment history tracking
+//! - Historical score snapshots for score-at-time queries
+//! - Read-only what-if score simulation
 
 #![no_std]
 
+use halo_credit_core as scoring;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
-    Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Bytes, BytesN, Env, Map, Symbol, Vec,
 };
 
+/// Subset of the Identity contract interface used to resolve wallet ownership
+#[allow(dead_code)]
+#[contractclient(name = "IdentityClient")]
+trait IdentityInterface {
+    fn get_id(env: Env, wallet: Address) -> Result<BytesN<32>, soroban_sdk::Error>;
+}
+
+/// Price source used to normalise payment volume across tokens.
+/// `price` returns the value of one whole token in reference units (6 decimals, USD).
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn price(env: Env, token: Address) -> i128;
+}
+
 /// Storage keys for the contract
 #[derive(Clone)]
 #[contracttype]
@@ -35,6 +59,50 @@ pub enum DataKey {
     PaymentHistory(BytesN<32>),
     /// Total number of users with credit scores
     UserCount,
+    /// Identity contract address (resolves wallet ownership)
+    IdentityContract,
+    /// Consent grant for a unique ID and relying party
+    Consent(BytesN<32>, Address),
+    /// Relying parties holding a consent grant for a unique ID
+    ConsentParties(BytesN<32>),
+    /// Access log for a unique ID
+    AccessLog(BytesN<32>),
+    /// Token used to pay query fees
+    FeeToken,
+    /// Per-query fee schedule
+    FeeSchedule,
+    /// Prepaid account for a relying party
+    Party(Address),
+    /// Collected query fees available to the admin
+    TreasuryBalance,
+    /// Sum of all relying party prepaid balances
+    PartyBalances,
+    /// List of lenders authorized to report loan events
+    LenderReporters,
+    /// Loan event history for a unique ID
+    LoanHistory(BytesN<32>),
+    /// Registered decimals for a payment token
+    TokenDecimals(Address),
+    /// Price oracle contract address
+    PriceOracle,
+    /// Score snapshots for a unique ID
+    ScoreHistory(BytesN<32>),
+    /// Credit freeze placed by the owner of a unique ID
+    Freeze(BytesN<32>),
+    /// Aggregate statistics across all users
+    Stats,
+    /// Unexpired negative items for a unique ID
+    NegativeItems(BytesN<32>),
+    /// How long negative items count towards the score (seconds)
+    NegativeItemHorizon,
+    /// Score certificate by commitment
+    Certificate(BytesN<32>),
+    /// Rightmost filled node per level of the certificate Merkle tree
+    CertificateFrontier,
+    /// Current root of the certificate Merkle tree
+    CertificateRoot,
+    /// Longest a score certificate may stay valid (seconds)
+    MaxCertificateLifetime,
 }
 
 /// Contract errors
@@ -54,6 +122,54 @@ pub enum CreditError {
     ContractAlreadyAuthorized = 5,
     /// Invalid score value
     InvalidScore = 6,
+    /// Identity contract has not been configured
+    IdentityNotConfigured = 7,
+    /// No consent granted to the relying party
+    ConsentNotFound = 8,
+    /// Consent grant has expired
+    ConsentExpired = 9,
+    /// Consent grant does not cover the requested scope
+    ConsentScopeInsufficient = 10,
+    /// Invalid consent expiry
+    InvalidExpiry = 11,
+    /// Relying party has not registered an account
+    PartyNotRegistered = 12,
+    /// Relying party is already registered
+    PartyAlreadyRegistered = 13,
+    /// Insufficient prepaid or treasury balance
+    InsufficientBalance = 14,
+    /// Invalid amount
+    InvalidAmount = 15,
+    /// Fee token has not been configured
+    FeeTokenNotConfigured = 16,
+    /// Lender already authorized
+    LenderAlreadyAuthorized = 17,
+    /// Invalid token configuration
+    InvalidTokenConfig = 18,
+    /// Too many IDs in a batch query
+    BatchTooLarge = 19,
+    /// Wallet does not resolve to an identity
+    WalletNotBound = 20,
+    /// Credit profile is frozen by its owner
+    Frozen = 21,
+    /// Credit profile is already frozen
+    AlreadyFrozen = 22,
+    /// Credit profile is not frozen
+    NotFrozen = 23,
+    /// Unfreeze delay has not elapsed or no unfreeze was requested
+    UnfreezeNotReady = 24,
+    /// Invalid negative item horizon
+    InvalidHorizon = 25,
+    /// Score certificate not found
+    CertificateNotFound = 26,
+    /// Score certificate has expired
+    CertificateExpired = 27,
+    /// Certificate Merkle tree is full
+    CertificateTreeFull = 28,
+    /// Fee token cannot change while prepaid or treasury balances are held
+    FeeBalancesOutstanding = 29,
+    /// Lender has already reported this loan as charged off
+    ChargeOffAlreadyReported = 30,
 }
 
 /// Credit data stored for each user
@@ -76,7 +192,7 @@ pub struct CreditData {
     pub circles_completed: u32,
     /// Number of circles defaulted/failed
     pub circles_defaulted: u32,
-    /// Total volume transacted (in token units)
+    /// Total volume transacted (normalised to reference units, 6 decimals)
     pub total_volume: i128,
     /// Timestamp of last activity
     pub last_updated: u64,
@@ -84,6 +200,19 @@ pub struct CreditData {
     pub first_activity: u64,
     /// Score algorithm version
     pub score_version: u32,
+    /// Number of loans originated by external lenders
+    pub loans_originated: u32,
+    /// Number of loan installments paid on time
+    pub loan_payments_on_time: u32,
+    /// Number of loan installments paid late
+    pub loan_payments_late: u32,
+    /// Number of loans repaid in full
+    pub loans_repaid: u32,
+    /// Number of loans charged off
+    pub loans_charged_off: u32,
+    /// Volume transacted per token (in each token's own units), for up to
+    /// 20 tokens
+    pub token_volumes: Map<Address, i128>,
 }
 
 /// Individual payment record
@@ -94,7 +223,9 @@ pub struct PaymentRecord {
     pub circle_id: BytesN<32>,
     /// Round number in the circle
     pub round: u32,
-    /// Payment amount
+    /// Token the payment was made in
+    pub token: Address,
+    /// Payment amount (in token units)
     pub amount: i128,
     /// Whether payment was on time
     pub on_time: bool,
@@ -102,6 +233,95 @@ pub struct PaymentRecord {
     pub timestamp: u64,
 }
 
+/// Score at a point in time
+#[derive(Clone)]
+#[contracttype]
+pub struct ScoreSnapshot {
+    /// Timestamp of the snapshot
+    pub timestamp: u64,
+    /// Credit score at that time
+    pub score: u32,
+    /// Score algorithm version that produced the score
+    pub score_version: u32,
+}
+
+/// Loan event reported by an external lender
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum LoanEventKind {
+    /// Loan was originated
+    Originated,
+    /// Installment paid on time
+    PaidOnTime,
+    /// Installment paid late
+    PaidLate,
+    /// Loan repaid in full
+    Repaid,
+    /// Loan charged off as uncollectable
+    ChargedOff,
+}
+
+/// Individual loan event record
+#[derive(Clone)]
+#[contracttype]
+pub struct LoanRecord {
+    /// Lender that reported the event
+    pub lender: Address,
+    /// Lender's loan identifier
+    pub loan_id: BytesN<32>,
+    /// Event kind
+    pub kind: LoanEventKind,
+    /// Amount (principal for originations, installment otherwise)
+    pub amount: i128,
+    /// Timestamp of the event
+    pub timestamp: u64,
+}
+
+/// Kind of negative item that expires after the horizon
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum NegativeItemKind {
+    /// Missed circle payment
+    MissedPayment,
+    /// Circle ended in default
+    CircleDefault,
+    /// Loan charged off by a lender
+    LoanChargeOff,
+}
+
+/// Negative item counted in a user's credit data until it expires
+#[derive(Clone)]
+#[contracttype]
+pub struct NegativeItem {
+    /// Item kind
+    pub kind: NegativeItemKind,
+    /// Circle ID (missed payments and defaults) or loan ID (charge-offs)
+    pub reference: BytesN<32>,
+    /// Lender that reported the charge-off (`None` for circle items)
+    pub lender: Option<Address>,
+    /// Timestamp the item was recorded
+    pub timestamp: u64,
+}
+
+/// Hypothetical event applied by the score simulator.
+/// Payment amounts are in reference units (6 decimals, USD).
+#[derive(Clone)]
+#[contracttype]
+pub enum HypotheticalEvent {
+    /// Circle payment made on time
+    OnTimePayment(i128),
+    /// Circle payment made late
+    LatePayment(i128),
+    /// Circle payment missed
+    MissedPayment,
+    /// Circle completed successfully
+    CircleCompleted,
+    /// Circle defaulted
+    CircleDefaulted,
+    /// Loan event reported by a lender
+    Loan(LoanEventKind),
+}
+
 /// Score tier based on credit score
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -134,6 +354,199 @@ pub struct ScoreBreakdown {
     pub total: u32,
 }
 
+/// Credit freeze placed by a user on their profile
+#[derive(Clone)]
+#[contracttype]
+pub struct CreditFreeze {
+    /// Timestamp the freeze was placed
+    pub frozen_at: u64,
+    /// Time after which the unfreeze can be completed (0 if none requested)
+    pub unfreeze_at: u64,
+}
+
+/// Aggregate credit statistics, maintained on every write
+#[derive(Clone)]
+#[contracttype]
+pub struct CreditStats {
+    /// Number of users included in the distribution
+    pub users: u32,
+    /// Sum of all users' scores
+    pub score_sum: u64,
+    /// Number of users in the Building tier
+    pub building: u32,
+    /// Number of users in the Fair tier
+    pub fair: u32,
+    /// Number of users in the Good tier
+    pub good: u32,
+    /// Number of users in the Excellent tier
+    pub excellent: u32,
+    /// Users per 50-point bucket, starting at 300 (the last bucket is 800-850)
+    pub buckets: Vec<u32>,
+    /// Total on-time payments across all users
+    pub on_time_payments: u64,
+    /// Total late payments across all users
+    pub late_payments: u64,
+    /// Total missed payments across all users
+    pub missed_payments: u64,
+}
+
+/// Score certificate that can be checked without calling the contract.
+/// `commitment` is the SHA-256 of the canonical encoding defined in `halo-credit-core`.
+#[derive(Clone)]
+#[contracttype]
+pub struct ScoreCertificate {
+    /// Hash commitment to the certificate fields (also its Merkle leaf)
+    pub commitment: BytesN<32>,
+    /// SHA-256 of the unique ID and audience, unlinkable across audiences
+    pub pseudonym: BytesN<32>,
+    /// Audience the certificate was issued for
+    pub audience: BytesN<32>,
+    /// Credit score at issuance
+    pub score: u32,
+    /// Score tier at issuance
+    pub tier: ScoreTier,
+    /// Score algorithm version
+    pub score_version: u32,
+    /// Ledger sequence at issuance
+    pub ledger: u32,
+    /// Timestamp of issuance
+    pub issued_at: u64,
+    /// Timestamp the certificate expires at
+    pub expires_at: u64,
+    /// Position of the commitment in the certificate Merkle tree
+    pub index: u32,
+}
+
+/// Root of the certificate Merkle tree
+#[derive(Clone)]
+#[contracttype]
+pub struct CertificateRoot {
+    /// Merkle root
+    pub root: BytesN<32>,
+    /// Number of certificates in the tree
+    pub count: u32,
+    /// Ledger sequence the root was last updated at
+    pub ledger: u32,
+}
+
+/// Compact credit summary returned by batch queries
+#[derive(Clone)]
+#[contracttype]
+pub struct CreditSummary {
+    /// The user's unique ID
+    pub unique_id: BytesN<32>,
+    /// Credit score (300-850)
+    pub score: u32,
+    /// Score tier
+    pub tier: ScoreTier,
+    /// On-time payment rate (0-100)
+    pub on_time_rate: u32,
+    /// Number of circles completed successfully
+    pub circles_completed: u32,
+    /// Number of circles defaulted/failed
+    pub circles_defaulted: u32,
+    /// Timestamp of last activity
+    pub last_updated: u64,
+}
+
+/// Aggregate risk summary for a set of users (e.g. a circle's members)
+#[derive(Clone)]
+#[contracttype]
+pub struct RiskSummary {
+    /// Number of IDs with credit data
+    pub count: u32,
+    /// Number of IDs without credit data or with a frozen profile
+    pub unscored: u32,
+    /// Lowest score (0 if no IDs have credit data)
+    pub min_score: u32,
+    /// Average score, rounded down (0 if no IDs have credit data)
+    pub average_score: u32,
+    /// Number of users in the Building tier
+    pub building: u32,
+    /// Number of users in the Fair tier
+    pub fair: u32,
+    /// Number of users in the Good tier
+    pub good: u32,
+    /// Number of users in the Excellent tier
+    pub excellent: u32,
+}
+
+/// Access scope a user can grant to a relying party.
+/// Each scope also covers the ones listed before it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[contracttype]
+#[repr(u32)]
+pub enum ConsentScope {
+    /// Credit score only
+    Score = 0,
+    /// Score tier
+    Tier = 1,
+    /// Score breakdown by component
+    Breakdown = 2,
+    /// Full payment history
+    History = 3,
+}
+
+/// Consent granted by a user to a relying party
+#[derive(Clone)]
+#[contracttype]
+pub struct ConsentGrant {
+    /// Relying party allowed to query
+    pub party: Address,
+    /// Highest scope the party may query
+    pub scope: ConsentScope,
+    /// Timestamp the consent was granted
+    pub granted_at: u64,
+    /// Timestamp after which the consent is no longer valid
+    pub expires_at: u64,
+}
+
+/// Record of a relying party querying a user's credit
+#[derive(Clone)]
+#[contracttype]
+pub struct AccessRecord {
+    /// Relying party that made the query
+    pub party: Address,
+    /// Scope that was queried
+    pub scope: ConsentScope,
+    /// Timestamp of the query
+    pub timestamp: u64,
+}
+
+/// Fee charged per consented query, by scope (in fee token units)
+#[derive(Clone)]
+#[contracttype]
+pub struct FeeSchedule {
+    /// Fee for a score query
+    pub score: i128,
+    /// Fee for a tier query
+    pub tier: i128,
+    /// Fee for a breakdown query
+    pub breakdown: i128,
+    /// Fee for a history query
+    pub history: i128,
+}
+
+/// Prepaid account for a relying party
+#[derive(Clone)]
+#[contracttype]
+pub struct PartyAccount {
+    /// Remaining prepaid balance (in fee token units)
+    pub balance: i128,
+    /// Timestamp the party registered
+    pub registered_at: u64,
+    /// Number of score queries made
+    pub score_queries: u32,
+    /// Number of tier queries made
+    pub tier_queries: u32,
+    /// Number of breakdown queries made
+    pub breakdown_queries: u32,
+    /// Number of history queries made
+    pub history_queries: u32,
+    /// Total fees paid
+    pub total_fees_paid: i128,
+}
+
 #[contract]
 pub struct HaloCredit;
 
@@ -141,23 +554,28 @@ pub struct HaloCredit;
 impl HaloCredit {
     // ============ Constants ============
 
-    /// Base score for new users
-    const BASE_SCORE: u32 = 300;
-    /// Maximum possible score
-    const MAX_SCORE: u32 = 850;
-    /// Current score algorithm version
-    const SCORE_VERSION: u32 = 1;
-
-    /// Weight for payment history (40% = 220 points max)
-    const PAYMENT_HISTORY_MAX: u32 = 220;
-    /// Weight for circle completion (25% = 137 points max)
-    const CIRCLE_COMPLETION_MAX: u32 = 137;
-    /// Weight for volume (15% = 83 points max)
-    const VOLUME_MAX: u32 = 83;
-    /// Weight for tenure (10% = 55 points max)
-    const TENURE_MAX: u32 = 55;
-    /// Weight for attestation (10% = 55 points max)
-    const ATTESTATION_MAX: u32 = 55;
+    /// Default time negative items count towards the score (7 years)
+    const DEFAULT_NEGATIVE_ITEM_HORIZON: u64 = 7 * 365 * 24 * 60 * 60;
+    /// Delay between requesting and completing an unfreeze (24 hours)
+    const UNFREEZE_DELAY: u64 = 24 * 60 * 60;
+    /// Width of a score distribution bucket
+    const BUCKET_WIDTH: u32 = 50;
+    /// Number of score distribution buckets (300-850)
+    const BUCKET_COUNT: u32 = 11;
+    /// Maximum number of IDs in a batch query
+    const MAX_BATCH_SIZE: u32 = 50;
+    /// Maximum decimals accepted for a registered token
+    const MAX_TOKEN_DECIMALS: u32 = 18;
+    /// Score changes within this window share a single snapshot
+    const SNAPSHOT_INTERVAL: u64 = 24 * 60 * 60;
+    /// Maximum snapshots kept per user
+    const MAX_SNAPSHOTS: u32 = 365;
+    /// Maximum tokens tracked in a user's per-token volumes
+    const MAX_TRACKED_TOKENS: u32 = 20;
+    /// Maximum unexpired negative items kept per user
+    const MAX_NEGATIVE_ITEMS: u32 = 200;
+    /// Default longest validity of a score certificate (90 days)
+    const DEFAULT_MAX_CERTIFICATE_LIFETIME: u64 = 90 * 24 * 60 * 60;
 
     // ============ Admin Functions ============
 
@@ -247,476 +665,3109 @@ impl HaloCredit {
         Ok(())
     }
 
-    // ============ Score Update Functions (Authorized Only) ============
+    /// Add a lender to the list of authorized loan event reporters.
+    pub fn authorize_lender(env: Env, lender: Address) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
 
-    /// Record a payment. Called by Circle contract.
-    pub fn record_payment(
-        env: Env,
-        caller: Address,
-        unique_id: BytesN<32>,
-        circle_id: BytesN<32>,
-        round: u32,
-        amount: i128,
-        on_time: bool,
-    ) -> Result<u32, CreditError> {
-        Self::verify_authorized(&env, &caller)?;
+        admin.require_auth();
 
-        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
-        let current_time = env.ledger().timestamp();
+        let mut lenders: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::LenderReporters)
+            .unwrap_or(Vec::new(&env));
 
-        // Update payment stats
-        credit_data.total_payments += 1;
-        if on_time {
-            credit_data.on_time_payments += 1;
-        } else {
-            credit_data.late_payments += 1;
+        if lenders.contains(&lender) {
+            return Err(CreditError::LenderAlreadyAuthorized);
         }
-        credit_data.total_volume += amount;
-        credit_data.last_updated = current_time;
 
-        // Recalculate score
-        credit_data.score = Self::calculate_score(&env, &credit_data);
-
-        // Store updated credit data
+        lenders.push_back(lender.clone());
         env.storage()
-            .persistent()
-            .set(&DataKey::CreditScore(unique_id.clone()), &credit_data);
-
-        // Store payment record
-        let record = PaymentRecord {
-            circle_id,
-            round,
-            amount,
-            on_time,
-            timestamp: current_time,
-        };
-        Self::append_payment_record(&env, &unique_id, record);
-
-        // Extend TTL
-        env.storage().persistent().extend_ttl(
-            &DataKey::CreditScore(unique_id.clone()),
-            100 * 24 * 60 * 60,
-            100 * 24 * 60 * 60,
-        );
+            .instance()
+            .set(&DataKey::LenderReporters, &lenders);
 
-        env.events().publish(
-            (Symbol::new(&env, "payment_recorded"), symbol_short!("pay")),
-            (unique_id, on_time, credit_data.score),
-        );
+        env.events()
+            .publish((Symbol::new(&env, "lender_authorized"),), lender);
 
-        Ok(credit_data.score)
+        Ok(())
     }
 
-    /// Record a missed payment. Called by Circle contract.
-    pub fn record_missed_payment(
-        env: Env,
-        caller: Address,
-        unique_id: BytesN<32>,
-        circle_id: BytesN<32>,
-        round: u32,
-    ) -> Result<u32, CreditError> {
-        Self::verify_authorized(&env, &caller)?;
+    /// Remove a lender from the list of authorized loan event reporters.
+    pub fn revoke_lender(env: Env, lender: Address) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
 
-        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
-        let current_time = env.ledger().timestamp();
+        admin.require_auth();
 
-        // Update stats
-        credit_data.total_payments += 1;
-        credit_data.missed_payments += 1;
-        credit_data.last_updated = current_time;
+        let lenders: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::LenderReporters)
+            .unwrap_or(Vec::new(&env));
 
-        // Recalculate score
-        credit_data.score = Self::calculate_score(&env, &credit_data);
+        let mut new_lenders = Vec::new(&env);
+        for addr in lenders.iter() {
+            if addr != lender {
+                new_lenders.push_back(addr);
+            }
+        }
 
         env.storage()
-            .persistent()
-            .set(&DataKey::CreditScore(unique_id.clone()), &credit_data);
+            .instance()
+            .set(&DataKey::LenderReporters, &new_lenders);
 
-        env.events().publish(
-            (Symbol::new(&env, "payment_missed"), symbol_short!("miss")),
-            (unique_id.clone(), circle_id, round, credit_data.score),
-        );
+        env.events()
+            .publish((Symbol::new(&env, "lender_revoked"),), lender);
 
-        Ok(credit_data.score)
+        Ok(())
     }
 
-    /// Record circle completion. Called by Circle contract.
-    pub fn record_circle_completion(
-        env: Env,
-        caller: Address,
-        unique_id: BytesN<32>,
-        circle_id: BytesN<32>,
-        completed_successfully: bool,
-    ) -> Result<u32, CreditError> {
-        Self::verify_authorized(&env, &caller)?;
-
-        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
-        let current_time = env.ledger().timestamp();
-
-        if completed_successfully {
-            credit_data.circles_completed += 1;
-        } else {
-            credit_data.circles_defaulted += 1;
-        }
+    /// Set the Identity contract used to resolve wallet ownership.
+    pub fn set_identity_contract(env: Env, identity: Address) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
 
-        credit_data.last_updated = current_time;
-        credit_data.score = Self::calculate_score(&env, &credit_data);
+        admin.require_auth();
 
         env.storage()
-            .persistent()
-            .set(&DataKey::CreditScore(unique_id.clone()), &credit_data);
+            .instance()
+            .set(&DataKey::IdentityContract, &identity);
 
-        env.events().publish(
-            (Symbol::new(&env, "circle_completed"),),
-            (unique_id, circle_id, completed_successfully, credit_data.score),
-        );
+        env.events()
+            .publish((Symbol::new(&env, "identity_set"),), identity);
 
-        Ok(credit_data.score)
+        Ok(())
     }
 
-    // ============ Public Query Functions (SDK) ============
-
-    /// Get the credit score for a user. PUBLIC - used by SDK.
-    pub fn get_score(env: Env, unique_id: BytesN<32>) -> Option<u32> {
-        let credit_data: Option<CreditData> = env
+    /// Set the token used to pay query fees. The token can only change once
+    /// every prepaid balance and the treasury have been withdrawn.
+    pub fn set_fee_token(env: Env, fee_token: Address) -> Result<(), CreditError> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::CreditScore(unique_id));
-        credit_data.map(|d| d.score)
-    }
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
 
-    /// Get full credit data for a user. PUBLIC - used by SDK.
-    pub fn get_credit_data(env: Env, unique_id: BytesN<32>) -> Option<CreditData> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::CreditScore(unique_id))
-    }
+        admin.require_auth();
 
-    /// Get the score tier for a user. PUBLIC - used by SDK.
-    pub fn get_tier(env: Env, unique_id: BytesN<32>) -> Option<ScoreTier> {
-        let credit_data: Option<CreditData> = env
+        let party_balances: i128 = env
             .storage()
-            .persistent()
-            .get(&DataKey::CreditScore(unique_id));
+            .instance()
+            .get(&DataKey::PartyBalances)
+            .unwrap_or(0);
+        if party_balances != 0 || Self::get_treasury_balance(env.clone()) != 0 {
+            return Err(CreditError::FeeBalancesOutstanding);
+        }
 
-        credit_data.map(|d| Self::score_to_tier(d.score))
-    }
+        env.storage().instance().set(&DataKey::FeeToken, &fee_token);
 
-    /// Get detailed score breakdown. PUBLIC - used by SDK.
-    pub fn get_score_breakdown(env: Env, unique_id: BytesN<32>) -> Option<ScoreBreakdown> {
-        let credit_data: Option<CreditData> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::CreditScore(unique_id));
+        env.events()
+            .publish((Symbol::new(&env, "fee_token_set"),), fee_token);
 
-        credit_data.map(|d| Self::calculate_breakdown(&env, &d))
+        Ok(())
     }
 
-    /// Get payment history for a user. PUBLIC - used by SDK.
-    pub fn get_payment_history(env: Env, unique_id: BytesN<32>) -> Vec<PaymentRecord> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PaymentHistory(unique_id))
-            .unwrap_or(Vec::new(&env))
+    /// Set the per-query fee schedule. Fees must be non-negative.
+    pub fn set_fee_schedule(env: Env, schedule: FeeSchedule) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
+
+        admin.require_auth();
+
+        if schedule.score < 0 || schedule.tier < 0 || schedule.breakdown < 0 || schedule.history < 0
+        {
+            return Err(CreditError::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeSchedule, &schedule);
+
+        env.events().publish(
+            (Symbol::new(&env, "fee_schedule_set"),),
+            (
+                schedule.score,
+                schedule.tier,
+                schedule.breakdown,
+                schedule.history,
+            ),
+        );
+
+        Ok(())
     }
 
-    /// Get on-time payment rate (0-100). PUBLIC - used by SDK.
-    pub fn get_on_time_rate(env: Env, unique_id: BytesN<32>) -> Option<u32> {
-        let credit_data: Option<CreditData> = env
+    /// Withdraw collected query fees from the treasury.
+    pub fn withdraw_treasury(env: Env, to: Address, amount: i128) -> Result<(), CreditError> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::CreditScore(unique_id));
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
 
-        credit_data.map(|d| {
-            if d.total_payments == 0 {
-                100 // No payments means 100% (no late payments)
-            } else {
-                (d.on_time_payments * 100) / d.total_payments
-            }
-        })
-    }
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(CreditError::InvalidAmount);
+        }
+
+        let treasury: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryBalance)
+            .unwrap_or(0);
+        if treasury < amount {
+            return Err(CreditError::InsufficientBalance);
+        }
+
+        let fee_token = Self::get_fee_token(env.clone())?;
+        token::Client::new(&env, &fee_token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
 
-    /// Get total number of users with credit scores.
-    pub fn get_user_count(env: Env) -> u64 {
         env.storage()
             .instance()
-            .get(&DataKey::UserCount)
-            .unwrap_or(0)
+            .set(&DataKey::TreasuryBalance, &(treasury - amount));
+
+        env.events()
+            .publish((Symbol::new(&env, "treasury_withdrawn"),), (to, amount));
+
+        Ok(())
     }
 
-    /// Get admin address.
-    pub fn get_admin(env: Env) -> Result<Address, CreditError> {
+    /// Register the decimals of a payment token used for volume normalisation.
+    /// Unregistered tokens are assumed to use the reference decimals.
+    pub fn register_token(env: Env, token: Address, decimals: u32) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
+
+        admin.require_auth();
+
+        if decimals > Self::MAX_TOKEN_DECIMALS {
+            return Err(CreditError::InvalidTokenConfig);
+        }
+
         env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals(token.clone()), &decimals);
+
+        env.events()
+            .publish((Symbol::new(&env, "token_registered"),), (token, decimals));
+
+        Ok(())
+    }
+
+    /// Set the price oracle used to value non-reference tokens.
+    /// Without an oracle every token is valued at one reference unit.
+    pub fn set_price_oracle(env: Env, oracle: Address) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
             .instance()
             .get(&DataKey::Admin)
-            .ok_or(CreditError::NotInitialized)
+            .ok_or(CreditError::NotInitialized)?;
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::PriceOracle, &oracle);
+
+        env.events()
+            .publish((Symbol::new(&env, "price_oracle_set"),), oracle);
+
+        Ok(())
     }
 
-    /// Get list of authorized contracts.
-    pub fn get_authorized_contracts(env: Env) -> Vec<Address> {
+    /// Set how long negative items count towards the score. Only callable by admin.
+    /// Items already recorded expire against the new horizon on their next refresh.
+    pub fn set_negative_item_horizon(env: Env, horizon: u64) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
+
+        admin.require_auth();
+
+        if horizon == 0 {
+            return Err(CreditError::InvalidHorizon);
+        }
+
         env.storage()
             .instance()
-            .get(&DataKey::AuthorizedContracts)
-            .unwrap_or(Vec::new(&env))
+            .set(&DataKey::NegativeItemHorizon, &horizon);
+
+        env.events()
+            .publish((Symbol::new(&env, "horizon_set"),), horizon);
+
+        Ok(())
     }
 
-    // ============ Score Decay ============
+    /// Set the longest a score certificate may stay valid. Only callable by admin.
+    /// Certificates already issued keep their expiry.
+    pub fn set_max_certificate_lifetime(env: Env, lifetime: u64) -> Result<(), CreditError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)?;
 
-    /// Apply score decay for inactive users. Can be called by anyone.
-    pub fn apply_decay(env: Env, unique_id: BytesN<32>) -> Result<u32, CreditError> {
-        let mut credit_data: CreditData = env
+        admin.require_auth();
+
+        if lifetime == 0 {
+            return Err(CreditError::InvalidExpiry);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxCertificateLifetime, &lifetime);
+
+        env.events()
+            .publish((Symbol::new(&env, "cert_lifetime_set"),), lifetime);
+
+        Ok(())
+    }
+
+    // ============ Relying Party Accounts ============
+
+    /// Register a prepaid account for a relying party.
+    pub fn register_party(env: Env, party: Address) -> Result<(), CreditError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(CreditError::NotInitialized);
+        }
+
+        party.require_auth();
+
+        let key = DataKey::Party(party.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(CreditError::PartyAlreadyRegistered);
+        }
+
+        let account = PartyAccount {
+            balance: 0,
+            registered_at: env.ledger().timestamp(),
+            score_queries: 0,
+            tier_queries: 0,
+            breakdown_queries: 0,
+            history_queries: 0,
+            total_fees_paid: 0,
+        };
+        env.storage().persistent().set(&key, &account);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events()
+            .publish((Symbol::new(&env, "party_registered"),), party);
+
+        Ok(())
+    }
+
+    /// Deposit fee tokens into a relying party's prepaid balance.
+    pub fn deposit(env: Env, party: Address, amount: i128) -> Result<i128, CreditError> {
+        party.require_auth();
+
+        if amount <= 0 {
+            return Err(CreditError::InvalidAmount);
+        }
+
+        let key = DataKey::Party(party.clone());
+        let mut account: PartyAccount = env
             .storage()
             .persistent()
-            .get(&DataKey::CreditScore(unique_id.clone()))
-            .ok_or(CreditError::UserNotFound)?;
+            .get(&key)
+            .ok_or(CreditError::PartyNotRegistered)?;
 
-        let current_time = env.ledger().timestamp();
-        let days_inactive = (current_time - credit_data.last_updated) / 86400;
+        let fee_token = Self::get_fee_token(env.clone())?;
+        token::Client::new(&env, &fee_token).transfer(
+            &party,
+            env.current_contract_address(),
+            &amount,
+        );
 
-        // Decay 1 point per week of inactivity after 30 days grace period
-        if days_inactive > 30 {
-            let decay_weeks = (days_inactive - 30) / 7;
-            let decay_points = decay_weeks as u32;
+        account.balance += amount;
+        env.storage().persistent().set(&key, &account);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+        Self::adjust_party_balances(&env, amount);
 
-            // Don't decay below base score
-            if credit_data.score > Self::BASE_SCORE + decay_points {
-                credit_data.score -= decay_points;
-            } else {
-                credit_data.score = Self::BASE_SCORE;
-            }
+        env.events()
+            .publish((Symbol::new(&env, "party_deposit"),), (party, amount));
 
-            env.storage()
-                .persistent()
-                .set(&DataKey::CreditScore(unique_id.clone()), &credit_data);
+        Ok(account.balance)
+    }
 
-            env.events().publish(
-                (Symbol::new(&env, "score_decayed"),),
-                (unique_id, decay_points, credit_data.score),
-            );
+    /// Withdraw unused prepaid balance back to the relying party.
+    pub fn withdraw_deposit(env: Env, party: Address, amount: i128) -> Result<i128, CreditError> {
+        party.require_auth();
+
+        if amount <= 0 {
+            return Err(CreditError::InvalidAmount);
+        }
+
+        let key = DataKey::Party(party.clone());
+        let mut account: PartyAccount = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(CreditError::PartyNotRegistered)?;
+
+        if account.balance < amount {
+            return Err(CreditError::InsufficientBalance);
+        }
+
+        let fee_token = Self::get_fee_token(env.clone())?;
+        token::Client::new(&env, &fee_token).transfer(
+            &env.current_contract_address(),
+            &party,
+            &amount,
+        );
+
+        account.balance -= amount;
+        env.storage().persistent().set(&key, &account);
+        Self::adjust_party_balances(&env, -amount);
+
+        env.events()
+            .publish((Symbol::new(&env, "party_withdrawal"),), (party, amount));
+
+        Ok(account.balance)
+    }
+
+    // ============ Score Update Functions (Authorized Only) ============
+
+    /// Record a payment. Called by Circle contract.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_payment(
+        env: Env,
+        caller: Address,
+        unique_id: BytesN<32>,
+        circle_id: BytesN<32>,
+        round: u32,
+        token: Address,
+        amount: i128,
+        on_time: bool,
+    ) -> Result<u32, CreditError> {
+        Self::verify_authorized(&env, &caller)?;
+
+        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
+        let current_time = env.ledger().timestamp();
+
+        // Update payment stats. Payments the oracle can't price add no volume
+        // until they are re-priced off the event.
+        let volume = match Self::normalize_amount(&env, &token, amount) {
+            Some(volume) => volume,
+            None => {
+                env.events().publish(
+                    (Symbol::new(&env, "unpriced_payment"),),
+                    (unique_id.clone(), token.clone(), amount),
+                );
+                0
+            }
+        };
+        Self::apply_payment(&mut credit_data, on_time, volume);
+        match credit_data.token_volumes.get(token.clone()) {
+            Some(token_volume) => credit_data
+                .token_volumes
+                .set(token.clone(), token_volume + amount),
+            None if credit_data.token_volumes.len() < Self::MAX_TRACKED_TOKENS => {
+                credit_data.token_volumes.set(token.clone(), amount)
+            }
+            None => {}
         }
+        credit_data.last_updated = current_time;
+
+        // Recalculate score
+        credit_data.score = Self::calculate_score(&env, &credit_data);
+
+        // Store updated credit data
+        Self::store_credit_data(&env, &credit_data);
+
+        // Store payment record
+        let record = PaymentRecord {
+            circle_id,
+            round,
+            token,
+            amount,
+            on_time,
+            timestamp: current_time,
+        };
+        Self::append_payment_record(&env, &unique_id, record);
+
+        env.events().publish(
+            (Symbol::new(&env, "payment_recorded"), symbol_short!("pay")),
+            (unique_id, on_time, credit_data.score),
+        );
 
         Ok(credit_data.score)
     }
 
-    // ============ Internal Functions ============
+    /// Record a missed payment. Called by Circle contract.
+    pub fn record_missed_payment(
+        env: Env,
+        caller: Address,
+        unique_id: BytesN<32>,
+        circle_id: BytesN<32>,
+        round: u32,
+    ) -> Result<u32, CreditError> {
+        Self::verify_authorized(&env, &caller)?;
 
-    fn verify_authorized(env: &Env, caller: &Address) -> Result<(), CreditError> {
-        let authorized: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::AuthorizedContracts)
-            .ok_or(CreditError::NotInitialized)?;
+        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
+        let current_time = env.ledger().timestamp();
+
+        // Update stats
+        Self::apply_missed_payment(&mut credit_data);
+        credit_data.last_updated = current_time;
+
+        // Recalculate score
+        credit_data.score = Self::calculate_score(&env, &credit_data);
+
+        Self::store_credit_data(&env, &credit_data);
+        Self::append_negative_item(
+            &env,
+            &unique_id,
+            NegativeItemKind::MissedPayment,
+            &circle_id,
+            None,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "payment_missed"), symbol_short!("miss")),
+            (unique_id.clone(), circle_id, round, credit_data.score),
+        );
+
+        Ok(credit_data.score)
+    }
+
+    /// Record circle completion. Called by Circle contract.
+    pub fn record_circle_completion(
+        env: Env,
+        caller: Address,
+        unique_id: BytesN<32>,
+        circle_id: BytesN<32>,
+        completed_successfully: bool,
+    ) -> Result<u32, CreditError> {
+        Self::verify_authorized(&env, &caller)?;
+
+        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
+        let current_time = env.ledger().timestamp();
+
+        Self::apply_circle_completion(&mut credit_data, completed_successfully);
+
+        credit_data.last_updated = current_time;
+        credit_data.score = Self::calculate_score(&env, &credit_data);
+
+        Self::store_credit_data(&env, &credit_data);
+        if !completed_successfully {
+            Self::append_negative_item(
+                &env,
+                &unique_id,
+                NegativeItemKind::CircleDefault,
+                &circle_id,
+                None,
+            );
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "circle_completed"),),
+            (unique_id, circle_id, completed_successfully, credit_data.score),
+        );
+
+        Ok(credit_data.score)
+    }
+
+    // ============ Loan Reporting (Authorized Lenders Only) ============
+
+    /// Record a loan event. Called by an authorized lender.
+    pub fn record_loan_event(
+        env: Env,
+        lender: Address,
+        unique_id: BytesN<32>,
+        loan_id: BytesN<32>,
+        kind: LoanEventKind,
+        amount: i128,
+    ) -> Result<u32, CreditError> {
+        lender.require_auth();
+        Self::verify_lender(&env, &lender)?;
+
+        if amount < 0 {
+            return Err(CreditError::InvalidAmount);
+        }
+
+        // A loan can only be charged off once per lender
+        if kind == LoanEventKind::ChargedOff {
+            let items: Vec<NegativeItem> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::NegativeItems(unique_id.clone()))
+                .unwrap_or(Vec::new(&env));
+            let reported = items.iter().any(|item| {
+                item.kind == NegativeItemKind::LoanChargeOff
+                    && item.reference == loan_id
+                    && item.lender == Some(lender.clone())
+            });
+            if reported {
+                return Err(CreditError::ChargeOffAlreadyReported);
+            }
+        }
+
+        let mut credit_data = Self::get_or_create_credit_data(&env, &unique_id);
+        let current_time = env.ledger().timestamp();
+
+        Self::apply_loan_event(&mut credit_data, kind);
+        credit_data.last_updated = current_time;
+        credit_data.score = Self::calculate_score(&env, &credit_data);
+
+        Self::store_credit_data(&env, &credit_data);
+
+        let record = LoanRecord {
+            lender: lender.clone(),
+            loan_id: loan_id.clone(),
+            kind,
+            amount,
+            timestamp: current_time,
+        };
+        Self::append_loan_record(&env, &unique_id, record);
+        if kind == LoanEventKind::ChargedOff {
+            Self::append_negative_item(
+                &env,
+                &unique_id,
+                NegativeItemKind::LoanChargeOff,
+                &loan_id,
+                Some(lender.clone()),
+            );
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "loan_event"), symbol_short!("loan")),
+            (unique_id, lender, loan_id, kind, credit_data.score),
+        );
+
+        Ok(credit_data.score)
+    }
+
+    // ============ Public Query Functions (SDK) ============
+    //
+    // The plain reads return nothing for a frozen profile, the same as for an unknown ID.
+
+    /// Get the credit score for a user. PUBLIC - used by SDK.
+    pub fn get_score(env: Env, unique_id: BytesN<32>) -> Option<u32> {
+        Self::check_not_frozen(&env, &unique_id).ok()?;
+
+        let credit_data: Option<CreditData> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id));
+        credit_data.map(|d| d.score)
+    }
+
+    /// Get full credit data for a user. PUBLIC - used by SDK.
+    pub fn get_credit_data(env: Env, unique_id: BytesN<32>) -> Option<CreditData> {
+        Self::check_not_frozen(&env, &unique_id).ok()?;
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id))
+    }
+
+    /// Get the score tier for a user. PUBLIC - used by SDK.
+    pub fn get_tier(env: Env, unique_id: BytesN<32>) -> Option<ScoreTier> {
+        Self::check_not_frozen(&env, &unique_id).ok()?;
+
+        let credit_data: Option<CreditData> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id));
+
+        credit_data.map(|d| Self::score_to_tier(d.score))
+    }
+
+    /// Get detailed score breakdown. PUBLIC - used by SDK.
+    pub fn get_score_breakdown(env: Env, unique_id: BytesN<32>) -> Option<ScoreBreakdown> {
+        Self::check_not_frozen(&env, &unique_id).ok()?;
+
+        let credit_data: Option<CreditData> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id));
+
+        credit_data.map(|d| Self::calculate_breakdown(&env, &d))
+    }
+
+    /// Get the credit score for a wallet, resolved through the Identity contract.
+    /// PUBLIC - used by SDK.
+    pub fn get_score_by_wallet(env: Env, wallet: Address) -> Result<Option<u32>, CreditError> {
+        let unique_id = Self::resolve_wallet(&env, &wallet)?;
+        Self::check_not_frozen(&env, &unique_id)?;
+        Ok(Self::get_score(env, unique_id))
+    }
+
+    /// Get the score tier for a wallet, resolved through the Identity contract.
+    /// PUBLIC - used by SDK.
+    pub fn get_tier_by_wallet(env: Env, wallet: Address) -> Result<Option<ScoreTier>, CreditError> {
+        let unique_id = Self::resolve_wallet(&env, &wallet)?;
+        Self::check_not_frozen(&env, &unique_id)?;
+        Ok(Self::get_tier(env, unique_id))
+    }
+
+    /// Get the score breakdown for a wallet, resolved through the Identity contract.
+    /// PUBLIC - used by SDK.
+    pub fn get_breakdown_by_wallet(
+        env: Env,
+        wallet: Address,
+    ) -> Result<Option<ScoreBreakdown>, CreditError> {
+        let unique_id = Self::resolve_wallet(&env, &wallet)?;
+        Self::check_not_frozen(&env, &unique_id)?;
+        Ok(Self::get_score_breakdown(env, unique_id))
+    }
+
+    /// Get payment history for a user. PUBLIC - used by SDK.
+    pub fn get_payment_history(env: Env, unique_id: BytesN<32>) -> Vec<PaymentRecord> {
+        if Self::check_not_frozen(&env, &unique_id).is_err() {
+            return Vec::new(&env);
+        }
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::PaymentHistory(unique_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get loan event history for a user. PUBLIC - used by SDK.
+    pub fn get_loan_history(env: Env, unique_id: BytesN<32>) -> Vec<LoanRecord> {
+        if Self::check_not_frozen(&env, &unique_id).is_err() {
+            return Vec::new(&env);
+        }
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::LoanHistory(unique_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the most recent score snapshot at or before `timestamp`. PUBLIC - used by SDK.
+    pub fn get_score_at(env: Env, unique_id: BytesN<32>, timestamp: u64) -> Option<ScoreSnapshot> {
+        Self::check_not_frozen(&env, &unique_id).ok()?;
+
+        let history: Vec<ScoreSnapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ScoreHistory(unique_id))
+            .unwrap_or(Vec::new(&env));
+
+        history.iter().rev().find(|s| s.timestamp <= timestamp)
+    }
+
+    /// Get score snapshots taken between `from` and `to` (inclusive). PUBLIC - used by SDK.
+    pub fn get_score_series(
+        env: Env,
+        unique_id: BytesN<32>,
+        from: u64,
+        to: u64,
+    ) -> Vec<ScoreSnapshot> {
+        if Self::check_not_frozen(&env, &unique_id).is_err() {
+            return Vec::new(&env);
+        }
+
+        let history: Vec<ScoreSnapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ScoreHistory(unique_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut series = Vec::new(&env);
+        for snapshot in history.iter() {
+            if snapshot.timestamp >= from && snapshot.timestamp <= to {
+                series.push_back(snapshot);
+            }
+        }
+        series
+    }
+
+    /// Get on-time payment rate (0-100). PUBLIC - used by SDK.
+    pub fn get_on_time_rate(env: Env, unique_id: BytesN<32>) -> Option<u32> {
+        Self::check_not_frozen(&env, &unique_id).ok()?;
+
+        let credit_data: Option<CreditData> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id));
+
+        credit_data.map(|d| {
+            if d.total_payments == 0 {
+                100 // No payments means 100% (no late payments)
+            } else {
+                (d.on_time_payments * 100) / d.total_payments
+            }
+        })
+    }
+
+    // ============ Batch Query Functions (SDK) ============
+
+    /// Get credit scores for several users. Entries are `None` for users without credit data
+    /// or with a frozen profile. PUBLIC - used by SDK.
+    pub fn get_scores(
+        env: Env,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<Vec<Option<u32>>, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+
+        let mut scores = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            scores.push_back(Self::get_score(env.clone(), unique_id));
+        }
+        Ok(scores)
+    }
+
+    /// Get score tiers for several users. Entries are `None` for users without credit data
+    /// or with a frozen profile. PUBLIC - used by SDK.
+    pub fn get_tiers(
+        env: Env,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<Vec<Option<ScoreTier>>, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+
+        let mut tiers = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            tiers.push_back(Self::get_tier(env.clone(), unique_id));
+        }
+        Ok(tiers)
+    }
+
+    /// Get compact credit summaries for several users. Entries are `None` for users without
+    /// credit data or with a frozen profile. PUBLIC - used by SDK.
+    pub fn get_credit_summaries(
+        env: Env,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<Vec<Option<CreditSummary>>, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+
+        let mut summaries = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            let credit_data = Self::get_credit_data(env.clone(), unique_id);
+            summaries.push_back(credit_data.map(|d| Self::to_summary(&d)));
+        }
+        Ok(summaries)
+    }
+
+    /// Get the min/average score and tier counts for a set of users. Frozen profiles count
+    /// as unscored. PUBLIC - used by SDK.
+    pub fn get_risk_summary(
+        env: Env,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<RiskSummary, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+
+        let mut scores = Vec::new(&env);
+        let mut unscored = 0;
+        for unique_id in unique_ids.iter() {
+            match Self::get_score(env.clone(), unique_id) {
+                Some(score) => scores.push_back(score),
+                None => unscored += 1,
+            }
+        }
+        Ok(Self::to_risk_summary(&scores, unscored))
+    }
+
+    /// Get aggregate statistics and the score distribution. PUBLIC - used by SDK.
+    pub fn get_credit_stats(env: Env) -> CreditStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::Stats)
+            .unwrap_or(Self::empty_stats(&env))
+    }
+
+    /// Get the average score across all users (0 if there are none). PUBLIC - used by SDK.
+    pub fn get_average_score(env: Env) -> u32 {
+        let stats = Self::get_credit_stats(env);
+        stats.score_sum.checked_div(stats.users as u64).unwrap_or(0) as u32
+    }
+
+    /// Get the version of the scoring algorithm in use.
+    pub fn get_score_version(_env: Env) -> u32 {
+        scoring::SCORE_VERSION
+    }
+
+    /// Get total number of users with credit scores.
+    pub fn get_user_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UserCount)
+            .unwrap_or(0)
+    }
+
+    /// Get identity contract address.
+    pub fn get_identity_contract(env: Env) -> Result<Address, CreditError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::IdentityContract)
+            .ok_or(CreditError::IdentityNotConfigured)
+    }
+
+    /// Get the token used to pay query fees.
+    pub fn get_fee_token(env: Env) -> Result<Address, CreditError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeToken)
+            .ok_or(CreditError::FeeTokenNotConfigured)
+    }
+
+    /// Get the per-query fee schedule (all zero until configured).
+    pub fn get_fee_schedule(env: Env) -> FeeSchedule {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeSchedule)
+            .unwrap_or(FeeSchedule {
+                score: 0,
+                tier: 0,
+                breakdown: 0,
+                history: 0,
+            })
+    }
+
+    /// Get a relying party's prepaid account and usage counters.
+    pub fn get_party(env: Env, party: Address) -> Option<PartyAccount> {
+        env.storage().persistent().get(&DataKey::Party(party))
+    }
+
+    /// Get collected query fees not yet withdrawn.
+    pub fn get_treasury_balance(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TreasuryBalance)
+            .unwrap_or(0)
+    }
+
+    /// Get admin address.
+    pub fn get_admin(env: Env) -> Result<Address, CreditError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CreditError::NotInitialized)
+    }
+
+    /// Get list of authorized contracts.
+    pub fn get_authorized_contracts(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AuthorizedContracts)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get list of authorized lenders.
+    pub fn get_authorized_lenders(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::LenderReporters)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============ Simulation ============
+
+    /// Simulate the score after applying hypothetical events to a user's current
+    /// credit data. Read-only: nothing is written to storage.
+    pub fn simulate_score(
+        env: Env,
+        unique_id: BytesN<32>,
+        events: Vec<HypotheticalEvent>,
+    ) -> ScoreBreakdown {
+        let mut credit_data: CreditData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id.clone()))
+            .unwrap_or_else(|| Self::new_credit_data(&env, &unique_id));
+
+        for event in events.iter() {
+            match event {
+                HypotheticalEvent::OnTimePayment(amount) => {
+                    Self::apply_payment(&mut credit_data, true, amount)
+                }
+                HypotheticalEvent::LatePayment(amount) => {
+                    Self::apply_payment(&mut credit_data, false, amount)
+                }
+                HypotheticalEvent::MissedPayment => Self::apply_missed_payment(&mut credit_data),
+                HypotheticalEvent::CircleCompleted => {
+                    Self::apply_circle_completion(&mut credit_data, true)
+                }
+                HypotheticalEvent::CircleDefaulted => {
+                    Self::apply_circle_completion(&mut credit_data, false)
+                }
+                HypotheticalEvent::Loan(kind) => Self::apply_loan_event(&mut credit_data, kind),
+            }
+        }
+
+        Self::calculate_breakdown(&env, &credit_data)
+    }
+
+    // ============ Consent ============
+
+    /// Grant a relying party access to a user's credit up to `scope`.
+    /// Replaces any existing grant for the same party.
+    pub fn grant_consent(
+        env: Env,
+        owner: Address,
+        unique_id: BytesN<32>,
+        party: Address,
+        scope: ConsentScope,
+        expires_at: u64,
+    ) -> Result<(), CreditError> {
+        Self::verify_owner(&env, &owner, &unique_id)?;
+        Self::check_not_frozen(&env, &unique_id)?;
+
+        let current_time = env.ledger().timestamp();
+        if expires_at <= current_time {
+            return Err(CreditError::InvalidExpiry);
+        }
+
+        let grant = ConsentGrant {
+            party: party.clone(),
+            scope,
+            granted_at: current_time,
+            expires_at,
+        };
+
+        let key = DataKey::Consent(unique_id.clone(), party.clone());
+        env.storage().persistent().set(&key, &grant);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        let parties_key = DataKey::ConsentParties(unique_id.clone());
+        let mut parties: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&parties_key)
+            .unwrap_or(Vec::new(&env));
+        if !parties.contains(&party) {
+            parties.push_back(party.clone());
+            env.storage().persistent().set(&parties_key, &parties);
+        }
+        env.storage()
+            .persistent()
+            .extend_ttl(&parties_key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events().publish(
+            (Symbol::new(&env, "consent_granted"),),
+            (unique_id, party, scope, expires_at),
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a relying party's access to a user's credit.
+    pub fn revoke_consent(
+        env: Env,
+        owner: Address,
+        unique_id: BytesN<32>,
+        party: Address,
+    ) -> Result<(), CreditError> {
+        Self::verify_owner(&env, &owner, &unique_id)?;
+
+        let key = DataKey::Consent(unique_id.clone(), party.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(CreditError::ConsentNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        let parties_key = DataKey::ConsentParties(unique_id.clone());
+        let parties: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&parties_key)
+            .unwrap_or(Vec::new(&env));
+        let mut remaining = Vec::new(&env);
+        for addr in parties.iter() {
+            if addr != party {
+                remaining.push_back(addr);
+            }
+        }
+        env.storage().persistent().set(&parties_key, &remaining);
+
+        env.events()
+            .publish((Symbol::new(&env, "consent_revoked"),), (unique_id, party));
+
+        Ok(())
+    }
+
+    /// Get the consent granted to a relying party, if any.
+    pub fn get_consent(env: Env, unique_id: BytesN<32>, party: Address) -> Option<ConsentGrant> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Consent(unique_id, party))
+    }
+
+    /// Get all consent grants for a user, including expired ones.
+    pub fn get_consents(env: Env, unique_id: BytesN<32>) -> Vec<ConsentGrant> {
+        let parties: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ConsentParties(unique_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        let mut grants = Vec::new(&env);
+        for party in parties.iter() {
+            if let Some(grant) = env
+                .storage()
+                .persistent()
+                .get::<_, ConsentGrant>(&DataKey::Consent(unique_id.clone(), party))
+            {
+                grants.push_back(grant);
+            }
+        }
+        grants
+    }
+
+    /// Get the log of relying-party queries against a user's credit.
+    pub fn get_access_log(env: Env, unique_id: BytesN<32>) -> Vec<AccessRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessLog(unique_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============ Credit Freeze ============
+
+    /// Freeze a user's credit profile. Only callable by the wallet bound to the ID.
+    ///
+    /// While frozen, new consents can't be granted and consented, batch and wallet-addressed
+    /// queries fail with `Frozen`. Payment and loan reporting continue as normal.
+    /// Freezing again while an unfreeze is pending cancels the unfreeze.
+    pub fn freeze_credit(
+        env: Env,
+        owner: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<(), CreditError> {
+        Self::verify_owner(&env, &owner, &unique_id)?;
+
+        let key = DataKey::Freeze(unique_id.clone());
+        let freeze = match env.storage().persistent().get::<_, CreditFreeze>(&key) {
+            Some(freeze) if freeze.unfreeze_at == 0 => return Err(CreditError::AlreadyFrozen),
+            Some(freeze) => CreditFreeze {
+                unfreeze_at: 0,
+                ..freeze
+            },
+            None => CreditFreeze {
+                frozen_at: env.ledger().timestamp(),
+                unfreeze_at: 0,
+            },
+        };
+
+        env.storage().persistent().set(&key, &freeze);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        env.events()
+            .publish((Symbol::new(&env, "credit_frozen"),), unique_id);
+
+        Ok(())
+    }
+
+    /// Request to lift a freeze. The unfreeze can be completed once the delay has passed.
+    ///
+    /// # Returns
+    /// The time after which `complete_unfreeze` can be called
+    pub fn request_unfreeze(
+        env: Env,
+        owner: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<u64, CreditError> {
+        Self::verify_owner(&env, &owner, &unique_id)?;
+
+        let key = DataKey::Freeze(unique_id.clone());
+        let mut freeze: CreditFreeze = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(CreditError::NotFrozen)?;
+
+        freeze.unfreeze_at = env.ledger().timestamp() + Self::UNFREEZE_DELAY;
+        env.storage().persistent().set(&key, &freeze);
+
+        env.events().publish(
+            (Symbol::new(&env, "unfreeze_requested"),),
+            (unique_id, freeze.unfreeze_at),
+        );
+
+        Ok(freeze.unfreeze_at)
+    }
+
+    /// Lift a freeze after the unfreeze delay has passed.
+    pub fn complete_unfreeze(
+        env: Env,
+        owner: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<(), CreditError> {
+        Self::verify_owner(&env, &owner, &unique_id)?;
+
+        let key = DataKey::Freeze(unique_id.clone());
+        let freeze: CreditFreeze = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(CreditError::NotFrozen)?;
+
+        if freeze.unfreeze_at == 0 || env.ledger().timestamp() < freeze.unfreeze_at {
+            return Err(CreditError::UnfreezeNotReady);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((Symbol::new(&env, "credit_unfrozen"),), unique_id);
+
+        Ok(())
+    }
+
+    /// Get the freeze on a user's credit profile, if any.
+    pub fn get_freeze(env: Env, unique_id: BytesN<32>) -> Option<CreditFreeze> {
+        env.storage().persistent().get(&DataKey::Freeze(unique_id))
+    }
+
+    /// Check if a user's credit profile is frozen.
+    pub fn is_frozen(env: Env, unique_id: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::Freeze(unique_id))
+    }
+
+    // ============ Score Certificates ============
+
+    /// Issue a certificate of the user's current score for an audience (e.g. a partner on
+    /// another chain). Only callable by the wallet bound to the ID.
+    ///
+    /// The certificate names the user by an audience-specific pseudonym, and its commitment
+    /// is added to the certificate Merkle tree so it can be proven against a relayed root.
+    pub fn issue_score_certificate(
+        env: Env,
+        owner: Address,
+        unique_id: BytesN<32>,
+        audience: BytesN<32>,
+        expires_at: u64,
+    ) -> Result<ScoreCertificate, CreditError> {
+        Self::verify_owner(&env, &owner, &unique_id)?;
+        Self::check_not_frozen(&env, &unique_id)?;
+
+        let current_time = env.ledger().timestamp();
+        let max_expiry =
+            current_time.saturating_add(Self::get_max_certificate_lifetime(env.clone()));
+        if expires_at <= current_time || expires_at > max_expiry {
+            return Err(CreditError::InvalidExpiry);
+        }
+
+        let credit_data: CreditData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id.clone()))
+            .ok_or(CreditError::UserNotFound)?;
+
+        let count = Self::get_certificate_root(env.clone()).count;
+        if count >= 1 << scoring::CERTIFICATE_TREE_DEPTH {
+            return Err(CreditError::CertificateTreeFull);
+        }
+
+        let pseudonym_preimage =
+            scoring::pseudonym_preimage(&unique_id.to_array(), &audience.to_array());
+        let pseudonym = Self::sha256(&env, &pseudonym_preimage);
+
+        let fields = scoring::CertificateFields {
+            pseudonym: pseudonym.to_array(),
+            audience: audience.to_array(),
+            score: credit_data.score,
+            tier: scoring::score_to_tier(credit_data.score),
+            // The version of the algorithm that produced the stored score
+            score_version: credit_data.score_version,
+            ledger: env.ledger().sequence(),
+            issued_at: current_time,
+            expires_at,
+            index: count,
+        };
+        let commitment = Self::sha256(&env, &fields.preimage());
+
+        let certificate = ScoreCertificate {
+            commitment: commitment.clone(),
+            pseudonym,
+            audience,
+            score: fields.score,
+            tier: Self::score_to_tier(fields.score),
+            score_version: fields.score_version,
+            ledger: fields.ledger,
+            issued_at: fields.issued_at,
+            expires_at,
+            index: count,
+        };
+
+        let key = DataKey::Certificate(commitment.clone());
+        env.storage().persistent().set(&key, &certificate);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        let root = Self::insert_certificate_leaf(&env, &commitment, count);
+
+        env.events().publish(
+            (Symbol::new(&env, "certificate_issued"),),
+            (commitment, count, root),
+        );
+
+        Ok(certificate)
+    }
+
+    /// Check a certificate by its commitment.
+    ///
+    /// # Errors
+    /// * `CertificateNotFound` - No certificate with this commitment
+    /// * `CertificateExpired` - The certificate has expired
+    pub fn verify_score_certificate(
+        env: Env,
+        commitment: BytesN<32>,
+    ) -> Result<ScoreCertificate, CreditError> {
+        let certificate = Self::get_score_certificate(env.clone(), commitment)
+            .ok_or(CreditError::CertificateNotFound)?;
+
+        if env.ledger().timestamp() >= certificate.expires_at {
+            return Err(CreditError::CertificateExpired);
+        }
+
+        Ok(certificate)
+    }
+
+    /// Get a certificate by its commitment, whether or not it has expired.
+    pub fn get_score_certificate(env: Env, commitment: BytesN<32>) -> Option<ScoreCertificate> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Certificate(commitment))
+    }
+
+    /// Get the current root of the certificate Merkle tree.
+    pub fn get_certificate_root(env: Env) -> CertificateRoot {
+        env.storage()
+            .instance()
+            .get(&DataKey::CertificateRoot)
+            .unwrap_or_else(|| {
+                let mut root = BytesN::from_array(&env, &[0u8; 32]);
+                for _ in 0..scoring::CERTIFICATE_TREE_DEPTH {
+                    root = Self::hash_pair(&env, &root, &root);
+                }
+                CertificateRoot {
+                    root,
+                    count: 0,
+                    ledger: 0,
+                }
+            })
+    }
+
+    // ============ Consented Query Functions ============
+
+    /// Get the credit score for a user. Requires `Score` consent.
+    pub fn query_score(
+        env: Env,
+        party: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<u32, CreditError> {
+        let credit_data =
+            Self::consented_credit_data(&env, &party, &unique_id, ConsentScope::Score)?;
+        Ok(credit_data.score)
+    }
+
+    /// Get the score tier for a user. Requires `Tier` consent.
+    pub fn query_tier(
+        env: Env,
+        party: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<ScoreTier, CreditError> {
+        let credit_data =
+            Self::consented_credit_data(&env, &party, &unique_id, ConsentScope::Tier)?;
+        Ok(Self::score_to_tier(credit_data.score))
+    }
+
+    /// Get the score breakdown for a user. Requires `Breakdown` consent.
+    pub fn query_breakdown(
+        env: Env,
+        party: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<ScoreBreakdown, CreditError> {
+        let credit_data =
+            Self::consented_credit_data(&env, &party, &unique_id, ConsentScope::Breakdown)?;
+        Ok(Self::calculate_breakdown(&env, &credit_data))
+    }
+
+    /// Get the payment history for a user. Requires `History` consent.
+    pub fn query_history(
+        env: Env,
+        party: Address,
+        unique_id: BytesN<32>,
+    ) -> Result<Vec<PaymentRecord>, CreditError> {
+        Self::consented_credit_data(&env, &party, &unique_id, ConsentScope::History)?;
+        Ok(Self::get_payment_history(env, unique_id))
+    }
+
+    /// Get credit scores for several users. Requires `Score` consent for every ID;
+    /// each ID is charged and logged as a separate query.
+    pub fn query_scores(
+        env: Env,
+        party: Address,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<Vec<u32>, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+        party.require_auth();
+
+        let mut scores = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            let credit_data =
+                Self::read_with_consent(&env, &party, &unique_id, ConsentScope::Score)?;
+            scores.push_back(credit_data.score);
+        }
+        Ok(scores)
+    }
+
+    /// Get score tiers for several users. Requires `Tier` consent for every ID.
+    pub fn query_tiers(
+        env: Env,
+        party: Address,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<Vec<ScoreTier>, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+        party.require_auth();
+
+        let mut tiers = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            let credit_data =
+                Self::read_with_consent(&env, &party, &unique_id, ConsentScope::Tier)?;
+            tiers.push_back(Self::score_to_tier(credit_data.score));
+        }
+        Ok(tiers)
+    }
+
+    /// Get compact credit summaries for several users. Requires `Breakdown` consent for every ID.
+    pub fn query_credit_summaries(
+        env: Env,
+        party: Address,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<Vec<CreditSummary>, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+        party.require_auth();
+
+        let mut summaries = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            let credit_data =
+                Self::read_with_consent(&env, &party, &unique_id, ConsentScope::Breakdown)?;
+            summaries.push_back(Self::to_summary(&credit_data));
+        }
+        Ok(summaries)
+    }
+
+    /// Get the min/average score and tier counts for a set of users.
+    /// Requires `Tier` consent for every ID.
+    pub fn query_risk_summary(
+        env: Env,
+        party: Address,
+        unique_ids: Vec<BytesN<32>>,
+    ) -> Result<RiskSummary, CreditError> {
+        Self::check_batch_size(&unique_ids)?;
+        party.require_auth();
+
+        let mut scores = Vec::new(&env);
+        for unique_id in unique_ids.iter() {
+            let credit_data =
+                Self::read_with_consent(&env, &party, &unique_id, ConsentScope::Tier)?;
+            scores.push_back(credit_data.score);
+        }
+        Ok(Self::to_risk_summary(&scores, 0))
+    }
+
+    // ============ Negative Item Expiry ============
+
+    /// Roll expired negative items out of a user's counters and re-score. Can be called
+    /// by anyone (e.g. a keeper). Emits `item_expired` for each item rolled out.
+    pub fn refresh_score(env: Env, unique_id: BytesN<32>) -> Result<u32, CreditError> {
+        let mut credit_data: CreditData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id.clone()))
+            .ok_or(CreditError::UserNotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        let horizon = Self::get_negative_item_horizon(env.clone());
+
+        let key = DataKey::NegativeItems(unique_id.clone());
+        let mut items: Vec<NegativeItem> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let previous = credit_data.clone();
+        let count = items.len();
+
+        // Items are stored oldest first
+        while let Some(item) = items.first() {
+            if item.timestamp.saturating_add(horizon) > current_time {
+                break;
+            }
+            items.pop_front();
+
+            match item.kind {
+                NegativeItemKind::MissedPayment => {
+                    credit_data.missed_payments = credit_data.missed_payments.saturating_sub(1);
+                    credit_data.total_payments = credit_data.total_payments.saturating_sub(1);
+                }
+                NegativeItemKind::CircleDefault => {
+                    credit_data.circles_defaulted = credit_data.circles_defaulted.saturating_sub(1);
+                }
+                NegativeItemKind::LoanChargeOff => {
+                    credit_data.loans_charged_off = credit_data.loans_charged_off.saturating_sub(1);
+                }
+            }
+
+            env.events().publish(
+                (Symbol::new(&env, "item_expired"),),
+                (unique_id.clone(), item.kind, item.reference, item.timestamp),
+            );
+        }
+
+        // Nothing expired: leave the stored score (and any decay applied to it) alone
+        if items.len() == count {
+            return Ok(credit_data.score);
+        }
+
+        if items.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &items);
+        }
+
+        // Move the score by what the expired items were costing, so decay already
+        // applied to the stored score is kept
+        let recovered = Self::calculate_score(&env, &credit_data)
+            .saturating_sub(Self::calculate_score(&env, &previous));
+        credit_data.score = (credit_data.score + recovered).min(scoring::MAX_SCORE);
+        Self::store_credit_data(&env, &credit_data);
+
+        Ok(credit_data.score)
+    }
+
+    /// Get a user's unexpired negative items, oldest first. PUBLIC - used by SDK.
+    pub fn get_negative_items(env: Env, unique_id: BytesN<32>) -> Vec<NegativeItem> {
+        if Self::check_not_frozen(&env, &unique_id).is_err() {
+            return Vec::new(&env);
+        }
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::NegativeItems(unique_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get how long negative items count towards the score (seconds).
+    pub fn get_negative_item_horizon(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NegativeItemHorizon)
+            .unwrap_or(Self::DEFAULT_NEGATIVE_ITEM_HORIZON)
+    }
+
+    /// Get the longest a score certificate may stay valid (seconds).
+    pub fn get_max_certificate_lifetime(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxCertificateLifetime)
+            .unwrap_or(Self::DEFAULT_MAX_CERTIFICATE_LIFETIME)
+    }
+
+    // ============ Score Decay ============
+
+    /// Apply score decay for inactive users. Can be called by anyone.
+    pub fn apply_decay(env: Env, unique_id: BytesN<32>) -> Result<u32, CreditError> {
+        let mut credit_data: CreditData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id.clone()))
+            .ok_or(CreditError::UserNotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        let days_inactive = (current_time - credit_data.last_updated) / 86400;
+
+        // Decay 1 point per week of inactivity after 30 days grace period
+        if days_inactive > 30 {
+            let decay_weeks = (days_inactive - 30) / 7;
+            let decay_points = decay_weeks as u32;
+
+            // Don't decay below base score
+            if credit_data.score > scoring::BASE_SCORE + decay_points {
+                credit_data.score -= decay_points;
+            } else {
+                credit_data.score = scoring::BASE_SCORE;
+            }
+
+            Self::store_credit_data(&env, &credit_data);
+
+            env.events().publish(
+                (Symbol::new(&env, "score_decayed"),),
+                (unique_id, decay_points, credit_data.score),
+            );
+        }
+
+        Ok(credit_data.score)
+    }
+
+    // ============ Internal Functions ============
+
+    fn verify_authorized(env: &Env, caller: &Address) -> Result<(), CreditError> {
+        let authorized: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AuthorizedContracts)
+            .ok_or(CreditError::NotInitialized)?;
+
+        if !authorized.contains(caller) {
+            return Err(CreditError::Unauthorized);
+        }
+
+        Ok(())
+    }
+
+    fn verify_lender(env: &Env, lender: &Address) -> Result<(), CreditError> {
+        let lenders: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::LenderReporters)
+            .unwrap_or(Vec::new(env));
+
+        if !lenders.contains(lender) {
+            return Err(CreditError::Unauthorized);
+        }
+
+        Ok(())
+    }
+
+    fn verify_owner(env: &Env, owner: &Address, unique_id: &BytesN<32>) -> Result<(), CreditError> {
+        owner.require_auth();
+
+        // Wallets that aren't bound are treated the same as a mismatch
+        match Self::resolve_wallet(env, owner) {
+            Ok(id) if id == *unique_id => Ok(()),
+            Err(CreditError::IdentityNotConfigured) => Err(CreditError::IdentityNotConfigured),
+            _ => Err(CreditError::Unauthorized),
+        }
+    }
+
+    /// Look up a wallet's unique ID in the Identity contract. The lookup is made on every
+    /// call rather than cached, so any change to the wallet's binding applies immediately.
+    fn resolve_wallet(env: &Env, wallet: &Address) -> Result<BytesN<32>, CreditError> {
+        let identity: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::IdentityContract)
+            .ok_or(CreditError::IdentityNotConfigured)?;
+
+        match IdentityClient::new(env, &identity).try_get_id(wallet) {
+            Ok(Ok(id)) => Ok(id),
+            _ => Err(CreditError::WalletNotBound),
+        }
+    }
+
+    /// Check the party's consent for `scope`, load the user's credit data and log the access.
+    fn consented_credit_data(
+        env: &Env,
+        party: &Address,
+        unique_id: &BytesN<32>,
+        scope: ConsentScope,
+    ) -> Result<CreditData, CreditError> {
+        party.require_auth();
+        Self::read_with_consent(env, party, unique_id, scope)
+    }
+
+    /// Consent check, charge and access log for one ID. The caller must have
+    /// already required the party's auth.
+    fn read_with_consent(
+        env: &Env,
+        party: &Address,
+        unique_id: &BytesN<32>,
+        scope: ConsentScope,
+    ) -> Result<CreditData, CreditError> {
+        Self::check_not_frozen(env, unique_id)?;
+
+        let grant: ConsentGrant = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Consent(unique_id.clone(), party.clone()))
+            .ok_or(CreditError::ConsentNotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        if current_time >= grant.expires_at {
+            return Err(CreditError::ConsentExpired);
+        }
+        if grant.scope < scope {
+            return Err(CreditError::ConsentScopeInsufficient);
+        }
+
+        let credit_data: CreditData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditScore(unique_id.clone()))
+            .ok_or(CreditError::UserNotFound)?;
+
+        Self::charge_query(env, party, scope)?;
+
+        let record = AccessRecord {
+            party: party.clone(),
+            scope,
+            timestamp: current_time,
+        };
+        Self::append_access_record(env, unique_id, record);
+
+        env.events().publish(
+            (Symbol::new(env, "credit_queried"),),
+            (unique_id.clone(), party.clone(), scope),
+        );
+
+        Ok(credit_data)
+    }
+
+    /// Charge the scheduled fee for a query and update the party's usage counters.
+    /// Unregistered parties may only make queries that are free.
+    fn charge_query(env: &Env, party: &Address, scope: ConsentScope) -> Result<(), CreditError> {
+        let schedule = Self::get_fee_schedule(env.clone());
+        let fee = match scope {
+            ConsentScope::Score => schedule.score,
+            ConsentScope::Tier => schedule.tier,
+            ConsentScope::Breakdown => schedule.breakdown,
+            ConsentScope::History => schedule.history,
+        };
+
+        let key = DataKey::Party(party.clone());
+        let mut account: PartyAccount = match env.storage().persistent().get(&key) {
+            Some(account) => account,
+            None if fee == 0 => return Ok(()),
+            None => return Err(CreditError::PartyNotRegistered),
+        };
+
+        if account.balance < fee {
+            return Err(CreditError::InsufficientBalance);
+        }
+
+        account.balance -= fee;
+        account.total_fees_paid += fee;
+        match scope {
+            ConsentScope::Score => account.score_queries += 1,
+            ConsentScope::Tier => account.tier_queries += 1,
+            ConsentScope::Breakdown => account.breakdown_queries += 1,
+            ConsentScope::History => account.history_queries += 1,
+        }
+        env.storage().persistent().set(&key, &account);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        if fee > 0 {
+            Self::adjust_party_balances(env, -fee);
+            let treasury: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::TreasuryBalance)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::TreasuryBalance, &(treasury + fee));
+        }
+
+        Ok(())
+    }
+
+    fn adjust_party_balances(env: &Env, delta: i128) {
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PartyBalances)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::PartyBalances, &(total + delta));
+    }
+
+    fn sha256(env: &Env, data: &[u8]) -> BytesN<32> {
+        env.crypto()
+            .sha256(&Bytes::from_slice(env, data))
+            .to_bytes()
+    }
+
+    fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(&left.to_array());
+        data[32..].copy_from_slice(&right.to_array());
+        Self::sha256(env, &data)
+    }
+
+    /// Append a leaf to the incremental certificate Merkle tree and return the new root.
+    /// Empty positions hash as zero leaves.
+    fn insert_certificate_leaf(env: &Env, leaf: &BytesN<32>, index: u32) -> BytesN<32> {
+        let mut frontier: Vec<BytesN<32>> = env
+            .storage()
+            .instance()
+            .get(&DataKey::CertificateFrontier)
+            .unwrap_or(Vec::new(env));
+
+        let mut zero = BytesN::from_array(env, &[0u8; 32]);
+        let mut node = leaf.clone();
+        let mut position = index;
+        for level in 0..scoring::CERTIFICATE_TREE_DEPTH {
+            if position.is_multiple_of(2) {
+                if level < frontier.len() {
+                    frontier.set(level, node.clone());
+                } else {
+                    frontier.push_back(node.clone());
+                }
+                node = Self::hash_pair(env, &node, &zero);
+            } else {
+                let left = frontier.get(level).unwrap_or(zero.clone());
+                node = Self::hash_pair(env, &left, &node);
+            }
+            zero = Self::hash_pair(env, &zero, &zero);
+            position /= 2;
+        }
+
+        let root = CertificateRoot {
+            root: node.clone(),
+            count: index + 1,
+            ledger: env.ledger().sequence(),
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::CertificateFrontier, &frontier);
+        env.storage()
+            .instance()
+            .set(&DataKey::CertificateRoot, &root);
+
+        node
+    }
+
+    fn check_not_frozen(env: &Env, unique_id: &BytesN<32>) -> Result<(), CreditError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Freeze(unique_id.clone()))
+        {
+            return Err(CreditError::Frozen);
+        }
+        Ok(())
+    }
+
+    fn check_batch_size(unique_ids: &Vec<BytesN<32>>) -> Result<(), CreditError> {
+        if unique_ids.len() > Self::MAX_BATCH_SIZE {
+            return Err(CreditError::BatchTooLarge);
+        }
+        Ok(())
+    }
+
+    fn to_summary(data: &CreditData) -> CreditSummary {
+        // No payments means 100% (no late payments)
+        let on_time_rate = (data.on_time_payments * 100)
+            .checked_div(data.total_payments)
+            .unwrap_or(100);
+
+        CreditSummary {
+            unique_id: data.unique_id.clone(),
+            score: data.score,
+            tier: Self::score_to_tier(data.score),
+            on_time_rate,
+            circles_completed: data.circles_completed,
+            circles_defaulted: data.circles_defaulted,
+            last_updated: data.last_updated,
+        }
+    }
+
+    fn to_risk_summary(scores: &Vec<u32>, unscored: u32) -> RiskSummary {
+        let mut summary = RiskSummary {
+            count: scores.len(),
+            unscored,
+            min_score: 0,
+            average_score: 0,
+            building: 0,
+            fair: 0,
+            good: 0,
+            excellent: 0,
+        };
+
+        let mut total: u64 = 0;
+        for score in scores.iter() {
+            total += score as u64;
+            if summary.min_score == 0 || score < summary.min_score {
+                summary.min_score = score;
+            }
+            match Self::score_to_tier(score) {
+                ScoreTier::Building => summary.building += 1,
+                ScoreTier::Fair => summary.fair += 1,
+                ScoreTier::Good => summary.good += 1,
+                ScoreTier::Excellent => summary.excellent += 1,
+            }
+        }
+        summary.average_score = total.checked_div(scores.len() as u64).unwrap_or(0) as u32;
+        summary
+    }
+
+    fn get_or_create_credit_data(env: &Env, unique_id: &BytesN<32>) -> CreditData {
+        let key = DataKey::CreditScore(unique_id.clone());
+
+        if let Some(data) = env.storage().persistent().get::<_, CreditData>(&key) {
+            return data;
+        }
+
+        let new_data = Self::new_credit_data(env, unique_id);
+
+        // Increment user count
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::UserCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::UserCount, &(count + 1));
+
+        new_data
+    }
+
+    fn new_credit_data(env: &Env, unique_id: &BytesN<32>) -> CreditData {
+        let current_time = env.ledger().timestamp();
+        CreditData {
+            unique_id: unique_id.clone(),
+            score: scoring::BASE_SCORE,
+            total_payments: 0,
+            on_time_payments: 0,
+            late_payments: 0,
+            missed_payments: 0,
+            circles_completed: 0,
+            circles_defaulted: 0,
+            total_volume: 0,
+            last_updated: current_time,
+            first_activity: current_time,
+            score_version: scoring::SCORE_VERSION,
+            loans_originated: 0,
+            loan_payments_on_time: 0,
+            loan_payments_late: 0,
+            loans_repaid: 0,
+            loans_charged_off: 0,
+            token_volumes: Map::new(env),
+        }
+    }
+
+    fn apply_payment(data: &mut CreditData, on_time: bool, volume: i128) {
+        data.total_payments += 1;
+        if on_time {
+            data.on_time_payments += 1;
+        } else {
+            data.late_payments += 1;
+        }
+        data.total_volume += volume;
+    }
+
+    fn apply_missed_payment(data: &mut CreditData) {
+        data.total_payments += 1;
+        data.missed_payments += 1;
+    }
+
+    fn apply_circle_completion(data: &mut CreditData, completed_successfully: bool) {
+        if completed_successfully {
+            data.circles_completed += 1;
+        } else {
+            data.circles_defaulted += 1;
+        }
+    }
+
+    fn apply_loan_event(data: &mut CreditData, kind: LoanEventKind) {
+        match kind {
+            LoanEventKind::Originated => data.loans_originated += 1,
+            LoanEventKind::PaidOnTime => data.loan_payments_on_time += 1,
+            LoanEventKind::PaidLate => data.loan_payments_late += 1,
+            LoanEventKind::Repaid => data.loans_repaid += 1,
+            LoanEventKind::ChargedOff => data.loans_charged_off += 1,
+        }
+    }
+
+    /// Convert a token amount to reference units using the token's registered
+    /// decimals and the oracle price. Returns `None` if the oracle has no price.
+    fn normalize_amount(env: &Env, token: &Address, amount: i128) -> Option<i128> {
+        let decimals: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenDecimals(token.clone()))
+            .unwrap_or(scoring::REFERENCE_DECIMALS);
+
+        let price = match env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PriceOracle)
+        {
+            Some(oracle) => match PriceOracleClient::new(env, &oracle).try_price(token) {
+                Ok(Ok(price)) if price > 0 => price,
+                _ => return None,
+            },
+            None => 10i128.pow(scoring::REFERENCE_DECIMALS),
+        };
+
+        Some(scoring::normalize_amount(amount, decimals, price))
+    }
+
+    /// Persist a user's credit data and snapshot its score.
+    fn store_credit_data(env: &Env, credit_data: &CreditData) {
+        // Every stored score was produced by the current algorithm
+        let mut credit_data = credit_data.clone();
+        credit_data.score_version = scoring::SCORE_VERSION;
+
+        let key = DataKey::CreditScore(credit_data.unique_id.clone());
+        let previous: Option<CreditData> = env.storage().persistent().get(&key);
+        Self::update_stats(env, previous.as_ref(), &credit_data);
+
+        env.storage().persistent().set(&key, &credit_data);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+
+        Self::append_score_snapshot(env, &credit_data);
+    }
+
+    fn empty_stats(env: &Env) -> CreditStats {
+        let mut buckets = Vec::new(env);
+        for _ in 0..Self::BUCKET_COUNT {
+            buckets.push_back(0);
+        }
+
+        CreditStats {
+            users: 0,
+            score_sum: 0,
+            building: 0,
+            fair: 0,
+            good: 0,
+            excellent: 0,
+            buckets,
+            on_time_payments: 0,
+            late_payments: 0,
+            missed_payments: 0,
+        }
+    }
+
+    /// Move a user's contribution to the aggregate statistics from `previous` to `current`.
+    fn update_stats(env: &Env, previous: Option<&CreditData>, current: &CreditData) {
+        let mut stats = Self::get_credit_stats(env.clone());
+
+        match previous {
+            Some(previous) => {
+                Self::count_score(&mut stats, previous.score, false);
+                stats.on_time_payments = stats
+                    .on_time_payments
+                    .saturating_sub(previous.on_time_payments as u64);
+                stats.late_payments = stats
+                    .late_payments
+                    .saturating_sub(previous.late_payments as u64);
+                stats.missed_payments = stats
+                    .missed_payments
+                    .saturating_sub(previous.missed_payments as u64);
+            }
+            None => stats.users += 1,
+        }
+
+        Self::count_score(&mut stats, current.score, true);
+        stats.on_time_payments += current.on_time_payments as u64;
+        stats.late_payments += current.late_payments as u64;
+        stats.missed_payments += current.missed_payments as u64;
+
+        env.storage().instance().set(&DataKey::Stats, &stats);
+    }
+
+    /// Add or remove a score from the sum, tier counts and buckets.
+    fn count_score(stats: &mut CreditStats, score: u32, add: bool) {
+        let adjust = |count: u32| {
+            if add {
+                count + 1
+            } else {
+                count.saturating_sub(1)
+            }
+        };
+
+        if add {
+            stats.score_sum += score as u64;
+        } else {
+            stats.score_sum = stats.score_sum.saturating_sub(score as u64);
+        }
+
+        match Self::score_to_tier(score) {
+            ScoreTier::Building => stats.building = adjust(stats.building),
+            ScoreTier::Fair => stats.fair = adjust(stats.fair),
+            ScoreTier::Good => stats.good = adjust(stats.good),
+            ScoreTier::Excellent => stats.excellent = adjust(stats.excellent),
+        }
+
+        let bucket = (score.saturating_sub(scoring::BASE_SCORE) / Self::BUCKET_WIDTH)
+            .min(Self::BUCKET_COUNT - 1);
+        let count = stats.buckets.get(bucket).unwrap_or(0);
+        stats.buckets.set(bucket, adjust(count));
+    }
+
+    fn calculate_score(env: &Env, data: &CreditData) -> u32 {
+        let breakdown = Self::calculate_breakdown(env, data);
+        breakdown.total
+    }
+
+    fn calculate_breakdown(env: &Env, data: &CreditData) -> ScoreBreakdown {
+        let inputs = scoring::ScoreInputs {
+            total_payments: data.total_payments,
+            on_time_payments: data.on_time_payments,
+            missed_payments: data.missed_payments,
+            circles_completed: data.circles_completed,
+            circles_defaulted: data.circles_defaulted,
+            total_volume: data.total_volume,
+            first_activity: data.first_activity,
+            loan_payments_on_time: data.loan_payments_on_time,
+            loan_payments_late: data.loan_payments_late,
+            loans_repaid: data.loans_repaid,
+            loans_charged_off: data.loans_charged_off,
+        };
+        let breakdown = scoring::calculate_breakdown(&inputs, env.ledger().timestamp());
+
+        ScoreBreakdown {
+            payment_history: breakdown.payment_history,
+            circle_completion: breakdown.circle_completion,
+            volume: breakdown.volume,
+            tenure: breakdown.tenure,
+            attestation: breakdown.attestation,
+            total: breakdown.total,
+        }
+    }
+
+    fn score_to_tier(score: u32) -> ScoreTier {
+        match scoring::score_to_tier(score) {
+            scoring::Tier::Building => ScoreTier::Building,
+            scoring::Tier::Fair => ScoreTier::Fair,
+            scoring::Tier::Good => ScoreTier::Good,
+            scoring::Tier::Excellent => ScoreTier::Excellent,
+        }
+    }
+
+    fn append_payment_record(env: &Env, unique_id: &BytesN<32>, record: PaymentRecord) {
+        let key = DataKey::PaymentHistory(unique_id.clone());
+        let mut history: Vec<PaymentRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        history.push_back(record);
+
+        // Keep only last 100 records to manage storage
+        if history.len() > 100 {
+            let start = history.len() - 100;
+            history = history.slice(start as u32..);
+        }
+
+        env.storage().persistent().set(&key, &history);
+
+        // Extend TTL
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+
+    fn append_score_snapshot(env: &Env, credit_data: &CreditData) {
+        let key = DataKey::ScoreHistory(credit_data.unique_id.clone());
+        let mut history: Vec<ScoreSnapshot> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        let snapshot = ScoreSnapshot {
+            timestamp: env.ledger().timestamp(),
+            score: credit_data.score,
+            score_version: credit_data.score_version,
+        };
+
+        // Coalesce changes within the same snapshot interval into one snapshot,
+        // keeping the interval's first timestamp so the bucket cannot slide forward
+        match history.last() {
+            Some(mut last)
+                if last.timestamp / Self::SNAPSHOT_INTERVAL
+                    == snapshot.timestamp / Self::SNAPSHOT_INTERVAL =>
+            {
+                last.score = snapshot.score;
+                last.score_version = snapshot.score_version;
+                history.set(history.len() - 1, last);
+            }
+            _ => history.push_back(snapshot),
+        }
+
+        if history.len() > Self::MAX_SNAPSHOTS {
+            let start = history.len() - Self::MAX_SNAPSHOTS;
+            history = history.slice(start..);
+        }
+
+        env.storage().persistent().set(&key, &history);
+
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+
+    fn append_loan_record(env: &Env, unique_id: &BytesN<32>, record: LoanRecord) {
+        let key = DataKey::LoanHistory(unique_id.clone());
+        let mut history: Vec<LoanRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        history.push_back(record);
+
+        // Keep only last 100 records to manage storage
+        if history.len() > 100 {
+            let start = history.len() - 100;
+            history = history.slice(start..);
+        }
+
+        env.storage().persistent().set(&key, &history);
+
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+
+    /// Each negative item stays until it expires so it can be rolled out of the
+    /// counters. Past the cap the oldest item is dropped and stays counted for good.
+    fn append_negative_item(
+        env: &Env,
+        unique_id: &BytesN<32>,
+        kind: NegativeItemKind,
+        reference: &BytesN<32>,
+        lender: Option<Address>,
+    ) {
+        let key = DataKey::NegativeItems(unique_id.clone());
+        let mut items: Vec<NegativeItem> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        items.push_back(NegativeItem {
+            kind,
+            reference: reference.clone(),
+            lender,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        if items.len() > Self::MAX_NEGATIVE_ITEMS {
+            let start = items.len() - Self::MAX_NEGATIVE_ITEMS;
+            items = items.slice(start..);
+        }
+
+        env.storage().persistent().set(&key, &items);
+
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+
+    fn append_access_record(env: &Env, unique_id: &BytesN<32>, record: AccessRecord) {
+        let key = DataKey::AccessLog(unique_id.clone());
+        let mut log: Vec<AccessRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        log.push_back(record);
+
+        // Keep only last 100 records to manage storage
+        if log.len() > 100 {
+            let start = log.len() - 100;
+            log = log.slice(start..);
+        }
+
+        env.storage().persistent().set(&key, &log);
+
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{token, Env};
+
+    fn create_unique_id(env: &Env, seed: u8) -> BytesN<32> {
+        let mut bytes = [0u8; 32];
+        bytes[0] = seed;
+        BytesN::from_array(env, &bytes)
+    }
+
+    fn create_circle_id(env: &Env, seed: u8) -> BytesN<32> {
+        let mut bytes = [0u8; 32];
+        bytes[0] = seed;
+        bytes[1] = 0xCC; // Mark as circle ID
+        BytesN::from_array(env, &bytes)
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        assert_eq!(client.get_admin(), admin);
+        assert_eq!(client.get_user_count(), 0);
+    }
+
+    #[test]
+    fn test_authorize_and_record_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+
+        // Record on-time payment
+        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        assert!(score >= 300);
+        assert!(client.get_score(&unique_id).is_some());
+        assert_eq!(client.get_user_count(), 1);
+
+        let credit_data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(credit_data.total_payments, 1);
+        assert_eq!(credit_data.on_time_payments, 1);
+    }
+
+    #[test]
+    fn test_late_payment_affects_score() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+
+        // Record late payment
+        let score_after_late = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &false);
+
+        // Record on-time payment for comparison
+        let unique_id_2 = create_unique_id(&env, 2);
+        let score_after_ontime = client.record_payment(&circle_contract, &unique_id_2, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        // On-time should have better score than late
+        assert!(score_after_ontime > score_after_late);
+    }
+
+    #[test]
+    fn test_circle_completion_bonus() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+
+        // Record initial payment
+        let initial_score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        // Record circle completion
+        let final_score = client.record_circle_completion(&circle_contract, &unique_id, &circle_id, &true);
+
+        // Score should improve after circle completion
+        assert!(final_score >= initial_score);
+
+        let credit_data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(credit_data.circles_completed, 1);
+    }
+
+    #[test]
+    fn test_score_tier() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+
+        // New user should be in Building tier
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        let tier = client.get_tier(&unique_id).unwrap();
+        // New users start around 300-500, so they're in Building or Fair
+        assert!(tier == ScoreTier::Building || tier == ScoreTier::Fair || tier == ScoreTier::Good);
+    }
+
+    #[test]
+    fn test_on_time_rate() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+
+        // 3 on-time, 1 late = 75% on-time rate
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &2, &usdc, &100_000_000, &true);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &3, &usdc, &100_000_000, &true);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &4, &usdc, &100_000_000, &false);
+
+        let rate = client.get_on_time_rate(&unique_id).unwrap();
+        assert_eq!(rate, 75);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_unauthorized_cannot_record() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let unauthorized = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // Try to record without authorization - should fail
+        client.record_payment(&unauthorized, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+    }
+
+    fn setup_identity(env: &Env, client: &HaloCreditClient, wallet: &Address, unique_id: &BytesN<32>) {
+        let identity_id = env.register_contract(None, halo_identity::HaloIdentity);
+        let identity = halo_identity::HaloIdentityClient::new(env, &identity_id);
+        identity.initialize(&Address::generate(env));
+        identity.bind_wallet(unique_id, wallet);
+        client.set_identity_contract(&identity_id);
+    }
+
+    #[test]
+    fn test_consented_queries() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        setup_identity(&env, &client, &owner, &unique_id);
+
+        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        client.grant_consent(&owner, &unique_id, &lender, &ConsentScope::Tier, &1_000);
+
+        assert_eq!(client.query_score(&lender, &unique_id), score);
+        assert_eq!(client.query_tier(&lender, &unique_id), client.get_tier(&unique_id).unwrap());
+        assert_eq!(
+            client.try_query_breakdown(&lender, &unique_id).err(),
+            Some(Ok(CreditError::ConsentScopeInsufficient))
+        );
+
+        let log = client.get_access_log(&unique_id);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.get(0).unwrap().party, lender);
+        assert_eq!(log.get(1).unwrap().scope, ConsentScope::Tier);
+
+        assert_eq!(client.get_consents(&unique_id).len(), 1);
+        client.revoke_consent(&owner, &unique_id, &lender);
+        assert!(client.get_consent(&unique_id, &lender).is_none());
+        assert_eq!(client.get_consents(&unique_id).len(), 0);
+        assert_eq!(
+            client.try_query_score(&lender, &unique_id),
+            Err(Ok(CreditError::ConsentNotFound))
+        );
+    }
+
+    #[test]
+    fn test_consent_expires() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        setup_identity(&env, &client, &owner, &unique_id);
+
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.grant_consent(&owner, &unique_id, &lender, &ConsentScope::History, &1_000);
+        assert_eq!(client.query_history(&lender, &unique_id).len(), 1);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_query_history(&lender, &unique_id).err(),
+            Some(Ok(CreditError::ConsentExpired))
+        );
+        assert_eq!(
+            client.try_grant_consent(&owner, &unique_id, &lender, &ConsentScope::Score, &1_000),
+            Err(Ok(CreditError::InvalidExpiry))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_only_owner_can_grant_consent() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+
+        client.initialize(&admin);
+        setup_identity(&env, &client, &owner, &unique_id);
+
+        client.grant_consent(&stranger, &unique_id, &lender, &ConsentScope::Score, &1_000);
+    }
+
+    #[test]
+    fn test_paid_queries() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+
+        let token_admin = Address::generate(&env);
+        let fee_token = env.register_stellar_asset_contract_v2(token_admin).address();
+        token::StellarAssetClient::new(&env, &fee_token).mint(&lender, &1_000);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        client.set_fee_token(&fee_token);
+        client.set_fee_schedule(&FeeSchedule {
+            score: 10,
+            tier: 5,
+            breakdown: 25,
+            history: 50,
+        });
+        setup_identity(&env, &client, &owner, &unique_id);
+
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.grant_consent(&owner, &unique_id, &lender, &ConsentScope::History, &1_000);
+
+        // Paid queries need a registered account
+        assert_eq!(
+            client.try_query_score(&lender, &unique_id).err(),
+            Some(Ok(CreditError::PartyNotRegistered))
+        );
+
+        client.register_party(&lender);
+        assert_eq!(client.deposit(&lender, &60), 60);
+
+        client.query_score(&lender, &unique_id);
+        client.query_tier(&lender, &unique_id);
+        client.query_breakdown(&lender, &unique_id);
+
+        // 20 left, not enough for a history query
+        assert_eq!(
+            client.try_query_history(&lender, &unique_id).err(),
+            Some(Ok(CreditError::InsufficientBalance))
+        );
+
+        let account = client.get_party(&lender).unwrap();
+        assert_eq!(account.balance, 20);
+        assert_eq!(account.score_queries, 1);
+        assert_eq!(account.tier_queries, 1);
+        assert_eq!(account.breakdown_queries, 1);
+        assert_eq!(account.history_queries, 0);
+        assert_eq!(account.total_fees_paid, 40);
+
+        // Free reads stay free
+        assert!(client.get_score(&unique_id).is_some());
+        assert_eq!(client.get_party(&lender).unwrap().balance, 20);
+
+        // The fee token is locked while balances are held in it
+        let other_token = Address::generate(&env);
+        assert_eq!(
+            client.try_set_fee_token(&other_token).err(),
+            Some(Ok(CreditError::FeeBalancesOutstanding))
+        );
+
+        assert_eq!(client.get_treasury_balance(), 40);
+        client.withdraw_treasury(&treasury, &40);
+        assert_eq!(client.get_treasury_balance(), 0);
+        assert_eq!(token::Client::new(&env, &fee_token).balance(&treasury), 40);
+        assert_eq!(
+            client.try_set_fee_token(&other_token).err(),
+            Some(Ok(CreditError::FeeBalancesOutstanding))
+        );
+
+        assert_eq!(client.withdraw_deposit(&lender, &20), 0);
+        assert_eq!(token::Client::new(&env, &fee_token).balance(&lender), 960);
+        client.set_fee_token(&other_token);
+    }
+
+    #[test]
+    fn test_loan_events_mixed_with_circle_history() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let reliable = create_unique_id(&env, 1);
+        let late_payer = create_unique_id(&env, 2);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+        let loan_id = create_circle_id(&env, 9);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        client.authorize_lender(&lender);
+
+        // Same circle history for both users
+        for id in [&reliable, &late_payer] {
+            client.record_payment(&circle_contract, id, &circle_id, &1, &usdc, &100_000_000, &true);
+            client.record_payment(&circle_contract, id, &circle_id, &2, &usdc, &100_000_000, &true);
+            client.record_loan_event(&lender, id, &loan_id, &LoanEventKind::Originated, &500_000_000);
+        }
+
+        client.record_loan_event(&lender, &reliable, &loan_id, &LoanEventKind::PaidOnTime, &50_000_000);
+        let reliable_score =
+            client.record_loan_event(&lender, &reliable, &loan_id, &LoanEventKind::Repaid, &50_000_000);
+
+        client.record_loan_event(&lender, &late_payer, &loan_id, &LoanEventKind::PaidLate, &50_000_000);
+        let late_score =
+            client.record_loan_event(&lender, &late_payer, &loan_id, &LoanEventKind::Repaid, &50_000_000);
+
+        assert!(reliable_score > late_score);
+
+        // One late installment outweighs one late circle payment
+        let breakdown = client.get_score_breakdown(&late_payer).unwrap();
+        assert_eq!(breakdown.payment_history, (2 * 100 / 4) * 220 / 100);
+        // Repaid loan counts as a completion
+        assert_eq!(breakdown.circle_completion, 137);
+
+        let data = client.get_credit_data(&late_payer).unwrap();
+        assert_eq!(data.loans_originated, 1);
+        assert_eq!(data.loan_payments_late, 1);
+        assert_eq!(data.loans_repaid, 1);
+        assert_eq!(data.total_payments, 2);
+        assert_eq!(client.get_loan_history(&late_payer).len(), 3);
+    }
+
+    #[test]
+    fn test_charge_off_outweighs_missed_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let missed = create_unique_id(&env, 1);
+        let charged_off = create_unique_id(&env, 2);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+        let loan_id = create_circle_id(&env, 9);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        client.authorize_lender(&lender);
+
+        client.record_payment(&circle_contract, &missed, &circle_id, &1, &usdc, &100_000_000, &true);
+        let missed_score = client.record_missed_payment(&circle_contract, &missed, &circle_id, &2);
+
+        client.record_payment(&circle_contract, &charged_off, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.record_loan_event(&lender, &charged_off, &loan_id, &LoanEventKind::PaidOnTime, &50_000_000);
+        let charged_off_score =
+            client.record_loan_event(&lender, &charged_off, &loan_id, &LoanEventKind::ChargedOff, &0);
+
+        assert!(charged_off_score < missed_score);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_unauthorized_lender_cannot_report() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let loan_id = create_circle_id(&env, 9);
+
+        client.initialize(&admin);
+        client.authorize_lender(&lender);
+        client.revoke_lender(&lender);
+
+        client.record_loan_event(&lender, &unique_id, &loan_id, &LoanEventKind::Originated, &500_000_000);
+    }
+
+    #[contract]
+    struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, token: Address, price: i128) {
+            env.storage().instance().set(&token, &price);
+        }
+
+        pub fn price(env: Env, token: Address) -> i128 {
+            env.storage().instance().get(&token).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_volume_normalised_by_decimals() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+        let seven_decimals = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        client.register_token(&seven_decimals, &7);
+
+        // 100 units of each token, no oracle: both valued at 1 reference unit
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &2, &seven_decimals, &1_000_000_000, &true);
+
+        let data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(data.total_volume, 200_000_000);
+        assert_eq!(data.token_volumes.get(usdc).unwrap(), 100_000_000);
+        assert_eq!(data.token_volumes.get(seven_decimals.clone()).unwrap(), 1_000_000_000);
+
+        let history = client.get_payment_history(&unique_id);
+        assert_eq!(history.get(1).unwrap().token, seven_decimals);
+
+        assert_eq!(
+            client.try_register_token(&seven_decimals, &19).err(),
+            Some(Ok(CreditError::InvalidTokenConfig))
+        );
+    }
+
+    #[test]
+    fn test_volume_priced_by_oracle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+        let xlm = Address::generate(&env);
+        let unpriced = Address::generate(&env);
 
-        if !authorized.contains(caller) {
-            return Err(CreditError::Unauthorized);
-        }
+        let oracle_id = env.register_contract(None, MockOracle);
+        let oracle = MockOracleClient::new(&env, &oracle_id);
+        oracle.set_price(&usdc, &1_000_000);
+        oracle.set_price(&xlm, &100_000); // $0.10
 
-        Ok(())
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        client.register_token(&xlm, &7);
+        client.set_price_oracle(&oracle_id);
+
+        // $100 in USDC and 1000 XLM ($100)
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &2, &xlm, &10_000_000_000, &true);
+        assert_eq!(client.get_credit_data(&unique_id).unwrap().total_volume, 200_000_000);
+
+        // A token the oracle can't price still counts as a payment, but adds no volume
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &3, &unpriced, &100_000_000, &true);
+        let data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(data.total_volume, 200_000_000);
+        assert_eq!(data.total_payments, 3);
+        assert_eq!(data.token_volumes.get(unpriced).unwrap(), 100_000_000);
+
+        // Per-token volumes stop growing once the cap is reached
+        for round in 4..30u32 {
+            let token = Address::generate(&env);
+            oracle.set_price(&token, &1_000_000);
+            client.record_payment(&circle_contract, &unique_id, &circle_id, &round, &token, &1_000_000, &true);
+        }
+        let data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(data.token_volumes.len(), 20);
+        assert_eq!(data.total_volume, 226_000_000);
+        assert_eq!(data.total_payments, 29);
     }
 
-    fn get_or_create_credit_data(env: &Env, unique_id: &BytesN<32>) -> CreditData {
-        let key = DataKey::CreditScore(unique_id.clone());
+    #[test]
+    fn test_score_snapshots() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        if let Some(data) = env.storage().persistent().get::<_, CreditData>(&key) {
-            return data;
-        }
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
 
-        // Create new credit data
-        let current_time = env.ledger().timestamp();
-        let new_data = CreditData {
-            unique_id: unique_id.clone(),
-            score: Self::BASE_SCORE,
-            total_payments: 0,
-            on_time_payments: 0,
-            late_payments: 0,
-            missed_payments: 0,
-            circles_completed: 0,
-            circles_defaulted: 0,
-            total_volume: 0,
-            last_updated: current_time,
-            first_activity: current_time,
-            score_version: Self::SCORE_VERSION,
-        };
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
-        // Increment user count
-        let count: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::UserCount)
-            .unwrap_or(0);
-        env.storage()
-            .instance()
-            .set(&DataKey::UserCount, &(count + 1));
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
 
-        new_data
+        let day = 86400;
+        env.ledger().with_mut(|li| li.timestamp = 10 * day);
+        let first = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        env.ledger().with_mut(|li| li.timestamp = 40 * day);
+        let second = client.record_missed_payment(&circle_contract, &unique_id, &circle_id, &2);
+
+        // Changes on the same day coalesce into one snapshot
+        env.ledger().with_mut(|li| li.timestamp = 70 * day);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &3, &usdc, &100_000_000, &false);
+        env.ledger().with_mut(|li| li.timestamp = 70 * day + 60);
+        let third = client.record_payment(&circle_contract, &unique_id, &circle_id, &4, &usdc, &100_000_000, &true);
+
+        assert!(client.get_score_at(&unique_id, &(5 * day)).is_none());
+        assert_eq!(client.get_score_at(&unique_id, &(10 * day)).unwrap().score, first);
+        assert_eq!(client.get_score_at(&unique_id, &(69 * day)).unwrap().score, second);
+        let latest = client.get_score_at(&unique_id, &(100 * day)).unwrap();
+        assert_eq!(latest.score, third);
+        assert_eq!(latest.score_version, scoring::SCORE_VERSION);
+
+        let series = client.get_score_series(&unique_id, &(20 * day), &(100 * day));
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.get(0).unwrap().score, second);
+        assert_eq!(series.get(1).unwrap().score, third);
+        assert_eq!(client.get_score_series(&unique_id, &0, &u64::MAX).len(), 3);
     }
 
-    fn calculate_score(env: &Env, data: &CreditData) -> u32 {
-        let breakdown = Self::calculate_breakdown(env, data);
-        breakdown.total
-    }
+    #[test]
+    fn test_score_version_follows_rescore() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-    fn calculate_breakdown(env: &Env, data: &CreditData) -> ScoreBreakdown {
-        // 1. Payment History (40% = max 220 points)
-        let payment_score = if data.total_payments > 0 {
-            // Include missed payments in calculation
-            let total = data.total_payments;
-            let on_time_ratio = (data.on_time_payments * 100) / total;
-
-            // Missed payments have severe penalty
-            let missed_penalty = if data.missed_payments > 0 {
-                (data.missed_payments * 30).min(100) // -30 points per missed, max -100
-            } else {
-                0
-            };
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
 
-            let base_payment_score = (on_time_ratio * Self::PAYMENT_HISTORY_MAX) / 100;
-            if base_payment_score > missed_penalty {
-                base_payment_score - missed_penalty
-            } else {
-                0
-            }
-        } else {
-            Self::PAYMENT_HISTORY_MAX / 2 // Neutral for no history
-        };
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
-        // 2. Circle Completion (25% = max 137 points)
-        let total_circles = data.circles_completed + data.circles_defaulted;
-        let completion_score = if total_circles > 0 {
-            let completion_ratio = (data.circles_completed * 100) / total_circles;
-            (completion_ratio * Self::CIRCLE_COMPLETION_MAX) / 100
-        } else {
-            Self::CIRCLE_COMPLETION_MAX / 2 // Neutral
-        };
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
 
-        // 3. Volume (15% = max 83 points) - Logarithmic scale
-        let volume_score = Self::calculate_volume_score(data.total_volume);
+        env.ledger().with_mut(|li| li.timestamp = 10 * 86400);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        // A profile scored by an older algorithm
+        env.as_contract(&contract_id, || {
+            let key = DataKey::CreditScore(unique_id.clone());
+            let mut data: CreditData = env.storage().persistent().get(&key).unwrap();
+            data.score_version = 1;
+            env.storage().persistent().set(&key, &data);
+
+            let key = DataKey::ScoreHistory(unique_id.clone());
+            let mut history: Vec<ScoreSnapshot> = env.storage().persistent().get(&key).unwrap();
+            let mut snapshot = history.get(0).unwrap();
+            snapshot.score_version = 1;
+            history.set(0, snapshot);
+            env.storage().persistent().set(&key, &history);
+        });
+        assert_eq!(client.get_credit_data(&unique_id).unwrap().score_version, 1);
+        assert_eq!(client.get_score_at(&unique_id, &(10 * 86400)).unwrap().score_version, 1);
+
+        // A same-day rescore replaces the old snapshot's version
+        env.ledger().with_mut(|li| li.timestamp = 10 * 86400 + 60);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &2, &usdc, &100_000_000, &true);
+        assert_eq!(
+            client.get_credit_data(&unique_id).unwrap().score_version,
+            scoring::SCORE_VERSION
+        );
 
-        // 4. Tenure (10% = max 55 points)
-        let current_time = env.ledger().timestamp();
-        let tenure_days = (current_time - data.first_activity) / 86400;
-        let tenure_score = if tenure_days > 365 {
-            Self::TENURE_MAX
-        } else {
-            ((tenure_days as u32) * Self::TENURE_MAX) / 365
-        };
+        env.ledger().with_mut(|li| li.timestamp = 40 * 86400);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &3, &usdc, &100_000_000, &true);
+        let series = client.get_score_series(&unique_id, &0, &u64::MAX);
+        assert_eq!(series.len(), 2);
+        for snapshot in series.iter() {
+            assert_eq!(snapshot.score_version, scoring::SCORE_VERSION);
+        }
+    }
 
-        // 5. Attestation (10% = max 55 points) - Neutral for now
-        let attestation_score = Self::ATTESTATION_MAX / 2;
+    #[test]
+    fn test_score_snapshots_bucket_by_day() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Calculate total (base 300)
-        let total = (Self::BASE_SCORE
-            + payment_score
-            + completion_score
-            + volume_score
-            + tenure_score
-            + attestation_score)
-            .min(Self::MAX_SCORE);
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
 
-        ScoreBreakdown {
-            payment_history: payment_score,
-            circle_completion: completion_score,
-            volume: volume_score,
-            tenure: tenure_score,
-            attestation: attestation_score,
-            total,
-        }
-    }
-
-    fn calculate_volume_score(volume: i128) -> u32 {
-        // Logarithmic scale for volume (assuming 6 decimal places for USDC)
-        // $100 = 20%, $1000 = 40%, $10000 = 60%, $100000 = 80%, $1M+ = 100%
-        let percentage = if volume < 100_000_000 {
-            // < $100
-            20
-        } else if volume < 1_000_000_000 {
-            // < $1000
-            40
-        } else if volume < 10_000_000_000 {
-            // < $10000
-            60
-        } else if volume < 100_000_000_000 {
-            // < $100000
-            80
-        } else {
-            100
-        };
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
-        (percentage * Self::VOLUME_MAX) / 100
-    }
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
 
-    fn score_to_tier(score: u32) -> ScoreTier {
-        if score < 450 {
-            ScoreTier::Building
-        } else if score < 600 {
-            ScoreTier::Fair
-        } else if score < 750 {
-            ScoreTier::Good
-        } else {
-            ScoreTier::Excellent
+        // Events every 12 hours for four days land in one snapshot per day
+        let hours = 3600;
+        for i in 0..8u64 {
+            env.ledger().with_mut(|li| li.timestamp = 10 * 86400 + i * 12 * hours);
+            client.record_payment(&circle_contract, &unique_id, &circle_id, &((i + 1) as u32), &usdc, &100_000_000, &true);
         }
-    }
-
-    fn append_payment_record(env: &Env, unique_id: &BytesN<32>, record: PaymentRecord) {
-        let key = DataKey::PaymentHistory(unique_id.clone());
-        let mut history: Vec<PaymentRecord> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-
-        history.push_back(record);
 
-        // Keep only last 100 records to manage storage
-        if history.len() > 100 {
-            let start = history.len() - 100;
-            history = history.slice(start as u32..);
+        let series = client.get_score_series(&unique_id, &0, &u64::MAX);
+        assert_eq!(series.len(), 4);
+        for (i, snapshot) in series.iter().enumerate() {
+            assert_eq!(snapshot.timestamp, (10 + i as u64) * 86400);
         }
+        let last = series.get(3).unwrap();
+        assert_eq!(Some(last.score), client.get_score(&unique_id));
+    }
 
-        env.storage().persistent().set(&key, &history);
+    #[test]
+    fn test_simulate_score() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Extend TTL
-        env.storage()
-            .persistent()
-            .extend_ttl(&key, 100 * 24 * 60 * 60, 100 * 24 * 60 * 60);
-    }
-}
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
-    fn create_unique_id(env: &Env, seed: u8) -> BytesN<32> {
-        let mut bytes = [0u8; 32];
-        bytes[0] = seed;
-        BytesN::from_array(env, &bytes)
-    }
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
 
-    fn create_circle_id(env: &Env, seed: u8) -> BytesN<32> {
-        let mut bytes = [0u8; 32];
-        bytes[0] = seed;
-        bytes[1] = 0xCC; // Mark as circle ID
-        BytesN::from_array(env, &bytes)
+        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        // Finishing the circle improves the score
+        let mut finish = Vec::new(&env);
+        finish.push_back(HypotheticalEvent::OnTimePayment(100_000_000));
+        finish.push_back(HypotheticalEvent::CircleCompleted);
+        let finished = client.simulate_score(&unique_id, &finish);
+        assert!(finished.total > score);
+
+        // Missing next month hurts it
+        let mut miss = Vec::new(&env);
+        miss.push_back(HypotheticalEvent::MissedPayment);
+        let missed = client.simulate_score(&unique_id, &miss);
+        assert!(missed.total < score);
+
+        // Simulating matches recording the same events for real
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &2, &usdc, &100_000_000, &true);
+        let recorded = client.record_circle_completion(&circle_contract, &unique_id, &circle_id, &true);
+        assert_eq!(finished.total, recorded);
+
+        // Nothing was written by the simulations
+        let data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(data.missed_payments, 0);
+        assert_eq!(data.total_payments, 2);
+
+        // Unknown users simulate from a fresh profile without being created
+        let newcomer = create_unique_id(&env, 2);
+        let mut loan = Vec::new(&env);
+        loan.push_back(HypotheticalEvent::Loan(LoanEventKind::PaidOnTime));
+        assert_eq!(client.simulate_score(&newcomer, &loan).payment_history, 220);
+        assert!(client.get_credit_data(&newcomer).is_none());
+        assert_eq!(client.get_user_count(), 1);
     }
 
     #[test]
-    fn test_initialize() {
+    fn test_batch_queries() {
         let env = Env::default();
+        env.mock_all_auths();
+
         let contract_id = env.register_contract(None, HaloCredit);
         let client = HaloCreditClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
         client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
 
-        assert_eq!(client.get_admin(), admin);
-        assert_eq!(client.get_user_count(), 0);
+        let identity_id = env.register_contract(None, halo_identity::HaloIdentity);
+        let identity = halo_identity::HaloIdentityClient::new(&env, &identity_id);
+        identity.initialize(&Address::generate(&env));
+        client.set_identity_contract(&identity_id);
+
+        let mut ids = Vec::new(&env);
+        let mut owners = Vec::new(&env);
+        for seed in 1..=3 {
+            let unique_id = create_unique_id(&env, seed);
+            let owner = Address::generate(&env);
+            identity.bind_wallet(&unique_id, &owner);
+            ids.push_back(unique_id);
+            owners.push_back(owner);
+        }
+
+        let good = client.record_payment(&circle_contract, &ids.get(0).unwrap(), &circle_id, &1, &usdc, &100_000_000, &true);
+        let poor = client.record_missed_payment(&circle_contract, &ids.get(1).unwrap(), &circle_id, &1);
+
+        // Public batch reads mirror get_score/get_tier
+        let scores = client.get_scores(&ids);
+        assert_eq!(scores.get(0).unwrap(), Some(good));
+        assert_eq!(scores.get(1).unwrap(), Some(poor));
+        assert_eq!(scores.get(2).unwrap(), None);
+
+        let tiers = client.get_tiers(&ids);
+        assert_eq!(tiers.get(0).unwrap(), client.get_tier(&ids.get(0).unwrap()));
+        assert_eq!(tiers.get(2).unwrap(), None);
+
+        let summaries = client.get_credit_summaries(&ids);
+        let summary = summaries.get(1).unwrap().unwrap();
+        assert_eq!(summary.score, poor);
+        assert_eq!(summary.on_time_rate, 0);
+
+        let risk = client.get_risk_summary(&ids);
+        assert_eq!(risk.count, 2);
+        assert_eq!(risk.unscored, 1);
+        assert_eq!(risk.min_score, poor.min(good));
+        assert_eq!(risk.average_score, (poor + good) / 2);
+        assert_eq!(risk.building + risk.fair + risk.good + risk.excellent, 2);
+
+        // Consented batch reads need a grant for every ID
+        let scored = Vec::from_array(&env, [ids.get(0).unwrap(), ids.get(1).unwrap()]);
+        client.grant_consent(&owners.get(0).unwrap(), &ids.get(0).unwrap(), &lender, &ConsentScope::Breakdown, &1_000);
+        assert_eq!(
+            client.try_query_scores(&lender, &scored).err(),
+            Some(Ok(CreditError::ConsentNotFound))
+        );
+        assert_eq!(client.get_access_log(&ids.get(0).unwrap()).len(), 0);
+
+        client.grant_consent(&owners.get(1).unwrap(), &ids.get(1).unwrap(), &lender, &ConsentScope::Tier, &1_000);
+        assert_eq!(client.query_scores(&lender, &scored), Vec::from_array(&env, [good, poor]));
+        assert_eq!(client.query_tiers(&lender, &scored).len(), 2);
+        assert_eq!(client.query_risk_summary(&lender, &scored).count, 2);
+        assert_eq!(
+            client.try_query_credit_summaries(&lender, &scored).err(),
+            Some(Ok(CreditError::ConsentScopeInsufficient))
+        );
+        assert_eq!(client.get_access_log(&ids.get(1).unwrap()).len(), 3);
+
+        let mut too_many = Vec::new(&env);
+        for _ in 0..51 {
+            too_many.push_back(ids.get(0).unwrap());
+        }
+        assert_eq!(
+            client.try_get_scores(&too_many).err(),
+            Some(Ok(CreditError::BatchTooLarge))
+        );
     }
 
     #[test]
-    fn test_authorize_and_record_payment() {
+    fn test_queries_by_wallet() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -725,26 +3776,36 @@ mod test {
 
         let admin = Address::generate(&env);
         let circle_contract = Address::generate(&env);
+        let wallet = Address::generate(&env);
         let unique_id = create_unique_id(&env, 1);
         let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
         client.initialize(&admin);
         client.authorize_contract(&circle_contract);
 
-        // Record on-time payment
-        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &100_000_000, &true);
+        assert_eq!(
+            client.try_get_score_by_wallet(&wallet).err(),
+            Some(Ok(CreditError::IdentityNotConfigured))
+        );
 
-        assert!(score >= 300);
-        assert!(client.get_score(&unique_id).is_some());
-        assert_eq!(client.get_user_count(), 1);
+        setup_identity(&env, &client, &wallet, &unique_id);
+        assert_eq!(client.get_score_by_wallet(&wallet), None);
 
-        let credit_data = client.get_credit_data(&unique_id).unwrap();
-        assert_eq!(credit_data.total_payments, 1);
-        assert_eq!(credit_data.on_time_payments, 1);
+        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+
+        assert_eq!(client.get_score_by_wallet(&wallet), Some(score));
+        assert_eq!(client.get_tier_by_wallet(&wallet), client.get_tier(&unique_id));
+        assert_eq!(client.get_breakdown_by_wallet(&wallet).unwrap().total, score);
+
+        assert_eq!(
+            client.try_get_tier_by_wallet(&Address::generate(&env)).err(),
+            Some(Ok(CreditError::WalletNotBound))
+        );
     }
 
     #[test]
-    fn test_late_payment_affects_score() {
+    fn test_credit_freeze() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -753,25 +3814,121 @@ mod test {
 
         let admin = Address::generate(&env);
         let circle_contract = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let lender = Address::generate(&env);
         let unique_id = create_unique_id(&env, 1);
         let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
         client.initialize(&admin);
         client.authorize_contract(&circle_contract);
+        setup_identity(&env, &client, &owner, &unique_id);
 
-        // Record late payment
-        let score_after_late = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &100_000_000, &false);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.grant_consent(&owner, &unique_id, &lender, &ConsentScope::Score, &100_000);
 
-        // Record on-time payment for comparison
-        let unique_id_2 = create_unique_id(&env, 2);
-        let score_after_ontime = client.record_payment(&circle_contract, &unique_id_2, &circle_id, &1, &100_000_000, &true);
+        client.freeze_credit(&owner, &unique_id);
+        assert!(client.is_frozen(&unique_id));
+        assert_eq!(
+            client.try_freeze_credit(&owner, &unique_id).err(),
+            Some(Ok(CreditError::AlreadyFrozen))
+        );
 
-        // On-time should have better score than late
-        assert!(score_after_ontime > score_after_late);
+        // Third-party reads and new consents are blocked
+        assert_eq!(
+            client.try_query_score(&lender, &unique_id).err(),
+            Some(Ok(CreditError::Frozen))
+        );
+        assert_eq!(
+            client.try_get_score_by_wallet(&owner).err(),
+            Some(Ok(CreditError::Frozen))
+        );
+        assert_eq!(
+            client.try_grant_consent(&owner, &unique_id, &Address::generate(&env), &ConsentScope::Score, &100_000).err(),
+            Some(Ok(CreditError::Frozen))
+        );
+
+        // The plain reads return nothing, as for an unknown ID
+        assert!(client.get_score(&unique_id).is_none());
+        assert!(client.get_credit_data(&unique_id).is_none());
+        assert!(client.get_tier(&unique_id).is_none());
+        assert!(client.get_score_breakdown(&unique_id).is_none());
+        assert!(client.get_on_time_rate(&unique_id).is_none());
+        assert!(client.get_score_at(&unique_id, &u64::MAX).is_none());
+        assert!(client.get_score_series(&unique_id, &0, &u64::MAX).is_empty());
+        assert!(client.get_payment_history(&unique_id).is_empty());
+        assert!(client.get_loan_history(&unique_id).is_empty());
+        assert!(client.get_negative_items(&unique_id).is_empty());
+
+        // Batch reads skip the frozen ID instead of failing
+        let other_id = create_unique_id(&env, 2);
+        client.record_payment(&circle_contract, &other_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        let ids = Vec::from_array(&env, [unique_id.clone(), other_id.clone()]);
+        let scores = client.get_scores(&ids);
+        assert_eq!(scores.get(0).unwrap(), None);
+        assert_eq!(scores.get(1).unwrap(), client.get_score(&other_id));
+        assert_eq!(client.get_tiers(&ids).get(0).unwrap(), None);
+        assert!(client.get_credit_summaries(&ids).get(0).unwrap().is_none());
+        let risk = client.get_risk_summary(&ids);
+        assert_eq!(risk.count, 1);
+        assert_eq!(risk.unscored, 1);
+
+        // Reporting continues
+        client.record_missed_payment(&circle_contract, &unique_id, &circle_id, &2);
+        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &3, &usdc, &100_000_000, &true);
+
+        // Unfreezing needs the delay
+        assert_eq!(
+            client.try_complete_unfreeze(&owner, &unique_id).err(),
+            Some(Ok(CreditError::UnfreezeNotReady))
+        );
+        let unfreeze_at = client.request_unfreeze(&owner, &unique_id);
+        assert_eq!(
+            client.try_complete_unfreeze(&owner, &unique_id).err(),
+            Some(Ok(CreditError::UnfreezeNotReady))
+        );
+
+        env.ledger().with_mut(|l| l.timestamp = unfreeze_at);
+        client.complete_unfreeze(&owner, &unique_id);
+        assert!(!client.is_frozen(&unique_id));
+        assert_eq!(client.query_score(&lender, &unique_id), score);
+        assert_eq!(client.get_score(&unique_id), Some(score));
+        assert_eq!(client.get_payment_history(&unique_id).len(), 2);
+        assert_eq!(client.get_negative_items(&unique_id).len(), 1);
+        assert_eq!(
+            client.try_request_unfreeze(&owner, &unique_id).err(),
+            Some(Ok(CreditError::NotFrozen))
+        );
     }
 
     #[test]
-    fn test_circle_completion_bonus() {
+    fn test_only_owner_can_unfreeze() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
+
+        let owner = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+
+        client.initialize(&Address::generate(&env));
+        setup_identity(&env, &client, &owner, &unique_id);
+
+        client.freeze_credit(&owner, &unique_id);
+        assert_eq!(
+            client.try_request_unfreeze(&Address::generate(&env), &unique_id).err(),
+            Some(Ok(CreditError::Unauthorized))
+        );
+
+        // Re-freezing cancels a pending unfreeze
+        client.request_unfreeze(&owner, &unique_id);
+        client.freeze_credit(&owner, &unique_id);
+        assert_eq!(client.get_freeze(&unique_id).unwrap().unfreeze_at, 0);
+    }
+
+    #[test]
+    fn test_credit_stats() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -780,27 +3937,123 @@ mod test {
 
         let admin = Address::generate(&env);
         let circle_contract = Address::generate(&env);
-        let unique_id = create_unique_id(&env, 1);
+        let user1 = create_unique_id(&env, 1);
+        let user2 = create_unique_id(&env, 2);
         let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
         client.initialize(&admin);
         client.authorize_contract(&circle_contract);
 
-        // Record initial payment
-        let initial_score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &100_000_000, &true);
+        assert_eq!(client.get_credit_stats().users, 0);
+        assert_eq!(client.get_average_score(), 0);
+
+        let score1 = client.record_payment(&circle_contract, &user1, &circle_id, &1, &usdc, &100_000_000, &true);
+        let score2 = client.record_payment(&circle_contract, &user2, &circle_id, &1, &usdc, &100_000_000, &false);
+
+        let stats = client.get_credit_stats();
+        assert_eq!(stats.users, 2);
+        assert_eq!(stats.on_time_payments, 1);
+        assert_eq!(stats.late_payments, 1);
+        assert_eq!(stats.score_sum, (score1 + score2) as u64);
+        assert_eq!(client.get_average_score(), (score1 + score2) / 2);
+        assert_eq!(stats.buckets.len(), 11);
+        assert_eq!(stats.buckets.get((score1 - 300) / 50).unwrap() + stats.buckets.get((score2 - 300) / 50).unwrap(), 2);
+
+        // Moving a score moves its bucket and tier rather than adding to them
+        let before = client.get_tier(&user1).unwrap();
+        for round in 2..=5 {
+            client.record_missed_payment(&circle_contract, &user1, &circle_id, &round);
+        }
+        let score1 = client.get_score(&user1).unwrap();
+        assert!(client.get_tier(&user1).unwrap() != before);
+
+        let stats = client.get_credit_stats();
+        assert_eq!(stats.users, 2);
+        assert_eq!(stats.missed_payments, 4);
+        assert_eq!(stats.score_sum, (score1 + score2) as u64);
+        assert_eq!(stats.building + stats.fair + stats.good + stats.excellent, 2);
+        let mut bucketed = 0;
+        for count in stats.buckets.iter() {
+            bucketed += count;
+        }
+        assert_eq!(bucketed, 2);
+    }
 
-        // Record circle completion
-        let final_score = client.record_circle_completion(&circle_contract, &unique_id, &circle_id, &true);
+    #[test]
+    fn test_negative_items_expire() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Score should improve after circle completion
-        assert!(final_score >= initial_score);
+        let contract_id = env.register_contract(None, HaloCredit);
+        let client = HaloCreditClient::new(&env, &contract_id);
 
-        let credit_data = client.get_credit_data(&unique_id).unwrap();
-        assert_eq!(credit_data.circles_completed, 1);
+        let admin = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let unique_id = create_unique_id(&env, 1);
+        let circle_id = create_circle_id(&env, 1);
+        let loan_id = create_circle_id(&env, 2);
+        let usdc = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        client.authorize_lender(&lender);
+        assert_eq!(client.get_negative_item_horizon(), 7 * 365 * 24 * 60 * 60);
+        assert_eq!(
+            client.try_set_negative_item_horizon(&0).err(),
+            Some(Ok(CreditError::InvalidHorizon))
+        );
+        client.set_negative_item_horizon(&1_000);
+
+        env.ledger().with_mut(|l| l.timestamp = 100);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.record_missed_payment(&circle_contract, &unique_id, &circle_id, &2);
+        client.record_circle_completion(&circle_contract, &unique_id, &circle_id, &false);
+
+        env.ledger().with_mut(|l| l.timestamp = 600);
+        client.record_loan_event(&lender, &unique_id, &loan_id, &LoanEventKind::ChargedOff, &0);
+        assert_eq!(client.get_negative_items(&unique_id).len(), 3);
+
+        // The same lender can't charge the loan off twice
+        assert_eq!(
+            client.try_record_loan_event(&lender, &unique_id, &loan_id, &LoanEventKind::ChargedOff, &0).err(),
+            Some(Ok(CreditError::ChargeOffAlreadyReported))
+        );
+        assert_eq!(client.get_credit_data(&unique_id).unwrap().loans_charged_off, 1);
+
+        // Nothing has expired yet
+        env.ledger().with_mut(|l| l.timestamp = 1_099);
+        let score = client.refresh_score(&unique_id);
+        assert_eq!(client.get_negative_items(&unique_id).len(), 3);
+
+        // Circle items expire first, the charge-off later
+        env.ledger().with_mut(|l| l.timestamp = 1_100);
+        let recovered = client.refresh_score(&unique_id);
+        assert!(recovered > score);
+
+        let data = client.get_credit_data(&unique_id).unwrap();
+        assert_eq!(data.missed_payments, 0);
+        assert_eq!(data.total_payments, 1);
+        assert_eq!(data.circles_defaulted, 0);
+        assert_eq!(data.loans_charged_off, 1);
+        let items = client.get_negative_items(&unique_id);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items.get(0).unwrap().kind, NegativeItemKind::LoanChargeOff);
+
+        env.ledger().with_mut(|l| l.timestamp = 1_600);
+        assert!(client.refresh_score(&unique_id) > recovered);
+        assert_eq!(client.get_credit_data(&unique_id).unwrap().loans_charged_off, 0);
+        assert_eq!(client.get_negative_items(&unique_id).len(), 0);
+
+        assert_eq!(
+            client.try_refresh_score(&create_unique_id(&env, 9)).err(),
+            Some(Ok(CreditError::UserNotFound))
+        );
     }
 
     #[test]
-    fn test_score_tier() {
+    fn test_negative_items_capped() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -815,16 +4068,20 @@ mod test {
         client.initialize(&admin);
         client.authorize_contract(&circle_contract);
 
-        // New user should be in Building tier
-        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &100_000_000, &true);
+        for round in 1..=205u32 {
+            env.ledger().with_mut(|l| l.timestamp = round as u64);
+            client.record_missed_payment(&circle_contract, &unique_id, &circle_id, &round);
+        }
 
-        let tier = client.get_tier(&unique_id).unwrap();
-        // New users start around 300-500, so they're in Building or Fair
-        assert!(tier == ScoreTier::Building || tier == ScoreTier::Fair || tier == ScoreTier::Good);
+        // The oldest items are dropped but stay counted
+        let items = client.get_negative_items(&unique_id);
+        assert_eq!(items.len(), 200);
+        assert_eq!(items.get(0).unwrap().timestamp, 6);
+        assert_eq!(client.get_credit_data(&unique_id).unwrap().missed_payments, 205);
     }
 
     #[test]
-    fn test_on_time_rate() {
+    fn test_refresh_score_keeps_decay() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -835,23 +4092,36 @@ mod test {
         let circle_contract = Address::generate(&env);
         let unique_id = create_unique_id(&env, 1);
         let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
         client.initialize(&admin);
         client.authorize_contract(&circle_contract);
-
-        // 3 on-time, 1 late = 75% on-time rate
-        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &100_000_000, &true);
-        client.record_payment(&circle_contract, &unique_id, &circle_id, &2, &100_000_000, &true);
-        client.record_payment(&circle_contract, &unique_id, &circle_id, &3, &100_000_000, &true);
-        client.record_payment(&circle_contract, &unique_id, &circle_id, &4, &100_000_000, &false);
-
-        let rate = client.get_on_time_rate(&unique_id).unwrap();
-        assert_eq!(rate, 75);
+        client.set_negative_item_horizon(&(200 * 86400));
+
+        env.ledger().with_mut(|l| l.timestamp = 86400);
+        client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        client.record_missed_payment(&circle_contract, &unique_id, &circle_id, &2);
+
+        // 100 days without activity decays the score by 10 points
+        env.ledger().with_mut(|l| l.timestamp = 101 * 86400);
+        let undecayed = client.get_score(&unique_id).unwrap();
+        let decayed = client.apply_decay(&unique_id);
+        assert_eq!(decayed, undecayed - 10);
+
+        // Nothing has expired, so a refresh leaves the decayed score alone
+        assert_eq!(client.refresh_score(&unique_id), decayed);
+        assert_eq!(client.get_score(&unique_id), Some(decayed));
+
+        // Expiring the missed payment adds back only what it was costing
+        env.ledger().with_mut(|l| l.timestamp = 201 * 86400);
+        let recovered = client.refresh_score(&unique_id);
+        assert!(recovered > decayed);
+        assert_eq!(client.get_credit_data(&unique_id).unwrap().missed_payments, 0);
+        assert!(recovered + 10 <= client.get_score_breakdown(&unique_id).unwrap().total);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")]
-    fn test_unauthorized_cannot_record() {
+    fn test_score_certificates() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -859,14 +4129,69 @@ mod test {
         let client = HaloCreditClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
-        let unauthorized = Address::generate(&env);
+        let circle_contract = Address::generate(&env);
+        let owner = Address::generate(&env);
         let unique_id = create_unique_id(&env, 1);
         let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
+        let audience_a = BytesN::from_array(&env, &[0xaa; 32]);
+        let audience_b = BytesN::from_array(&env, &[0xbb; 32]);
 
         client.initialize(&admin);
+        client.authorize_contract(&circle_contract);
+        setup_identity(&env, &client, &owner, &unique_id);
+
+        let score = client.record_payment(&circle_contract, &unique_id, &circle_id, &1, &usdc, &100_000_000, &true);
+        let empty_root = client.get_certificate_root();
+        assert_eq!(empty_root.count, 0);
+
+        let cert_a = client.issue_score_certificate(&owner, &unique_id, &audience_a, &1_000);
+        let cert_b = client.issue_score_certificate(&owner, &unique_id, &audience_b, &1_000);
+        assert_eq!(cert_a.score, score);
+        assert_eq!(cert_a.tier, client.get_tier(&unique_id).unwrap());
+        assert_eq!(cert_a.index, 0);
+        assert_eq!(cert_b.index, 1);
+        assert!(cert_a.pseudonym != cert_b.pseudonym);
+        assert!(cert_a.commitment != cert_b.commitment);
+
+        let root = client.get_certificate_root();
+        assert_eq!(root.count, 2);
+        assert!(root.root != empty_root.root);
+
+        assert_eq!(client.verify_score_certificate(&cert_a.commitment).score, score);
+        assert_eq!(
+            client.try_verify_score_certificate(&BytesN::from_array(&env, &[0; 32])).err(),
+            Some(Ok(CreditError::CertificateNotFound))
+        );
 
-        // Try to record without authorization - should fail
-        client.record_payment(&unauthorized, &unique_id, &circle_id, &1, &100_000_000, &true);
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+        assert_eq!(
+            client.try_verify_score_certificate(&cert_a.commitment).err(),
+            Some(Ok(CreditError::CertificateExpired))
+        );
+        assert!(client.get_score_certificate(&cert_a.commitment).is_some());
+
+        assert_eq!(
+            client.try_issue_score_certificate(&Address::generate(&env), &unique_id, &audience_a, &5_000).err(),
+            Some(Ok(CreditError::Unauthorized))
+        );
+        assert_eq!(
+            client.try_issue_score_certificate(&owner, &unique_id, &audience_a, &1_000).err(),
+            Some(Ok(CreditError::InvalidExpiry))
+        );
+
+        // Expiry is capped by the admin-set lifetime
+        assert_eq!(
+            client.try_issue_score_certificate(&owner, &unique_id, &audience_a, &u64::MAX).err(),
+            Some(Ok(CreditError::InvalidExpiry))
+        );
+        client.set_max_certificate_lifetime(&5_000);
+        assert_eq!(
+            client.try_issue_score_certificate(&owner, &unique_id, &audience_a, &6_001).err(),
+            Some(Ok(CreditError::InvalidExpiry))
+        );
+        let cert = client.issue_score_certificate(&owner, &unique_id, &audience_a, &6_000);
+        assert_eq!(cert.score_version, scoring::SCORE_VERSION);
     }
 
     #[test]
@@ -881,13 +4206,14 @@ mod test {
         let circle_contract = Address::generate(&env);
         let unique_id = create_unique_id(&env, 1);
         let circle_id = create_circle_id(&env, 1);
+        let usdc = Address::generate(&env);
 
         client.initialize(&admin);
         client.authorize_contract(&circle_contract);
 
         // Record multiple payments
         for round in 1..=5 {
-            client.record_payment(&circle_contract, &unique_id, &circle_id, &round, &100_000_000, &true);
+            client.record_payment(&circle_contract, &unique_id, &circle_id, &round, &usdc, &100_000_000, &true);
         }
 
         let history = client.get_payment_history(&unique_id);